@@ -0,0 +1,621 @@
+use crate::{Game, Lang};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::ops::RangeInclusive;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Request bodies are tiny; anything bigger is refused.
+const MAX_BODY: usize = 64 * 1024;
+/// How long a client may take to send its request before it is dropped.
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+/// How many games may be live at once, unless `with_max_sessions` says
+/// otherwise. Expired games are dropped before the limit is checked.
+const MAX_SESSIONS: usize = 1_000;
+
+/// A small HTTP/1.1 server speaking JSON, one game per session.
+///
+/// - `POST /games` with `{"min", "max", "seed", "max_attempts"}`, all
+///   optional, starts a game.
+/// - `POST /games/{id}/guesses` with `{"guess": 50}` scores a guess.
+/// - `GET /games/{id}` shows the game and its history.
+///
+/// Sessions that go unused for `ttl` are dropped, and new games are
+/// refused while `max_sessions` are still live.
+pub struct Api {
+    range: RangeInclusive<u32>,
+    max_attempts: Option<u32>,
+    ttl: Duration,
+    max_sessions: usize,
+    lang: Lang,
+    state: Mutex<State>,
+}
+
+struct State {
+    next_id: u64,
+    sessions: HashMap<u64, Session>,
+}
+
+struct Session {
+    game: Game,
+    seed: u64,
+    history: Vec<Turn>,
+    last_used: Instant,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    TooSmall,
+    TooBig,
+    Correct,
+}
+
+impl From<Ordering> for Verdict {
+    fn from(ordering: Ordering) -> Verdict {
+        match ordering {
+            Ordering::Less => Verdict::TooSmall,
+            Ordering::Greater => Verdict::TooBig,
+            Ordering::Equal => Verdict::Correct,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+struct Turn {
+    guess: u32,
+    result: Verdict,
+}
+
+/// The body of `POST /games`; missing fields use the server's defaults.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct NewGame {
+    min: Option<u32>,
+    max: Option<u32>,
+    seed: Option<u64>,
+    max_attempts: Option<u32>,
+}
+
+/// The body of `POST /games/{id}/guesses`. The guess may be a JSON number
+/// or a string, which is parsed exactly like a line typed in the terminal.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NewGuess {
+    guess: Value,
+}
+
+struct Request {
+    method: String,
+    path: String,
+    body: Vec<u8>,
+}
+
+struct Response {
+    status: u16,
+    body: Option<Value>,
+}
+
+impl Response {
+    fn json(status: u16, body: Value) -> Response {
+        Response {
+            status,
+            body: Some(body),
+        }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Response {
+        Response::json(status, json!({ "error": message.into() }))
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        204 => "No Content",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        413 => "Payload Too Large",
+        422 => "Unprocessable Entity",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    }
+}
+
+impl Api {
+    /// `range` and `max_attempts` apply to games that do not choose their own.
+    pub fn new(range: RangeInclusive<u32>, max_attempts: Option<u32>, ttl: Duration) -> Api {
+        Api {
+            range,
+            max_attempts,
+            ttl,
+            max_sessions: MAX_SESSIONS,
+            lang: Lang::En,
+            state: Mutex::new(State {
+                next_id: 1,
+                sessions: HashMap::new(),
+            }),
+        }
+    }
+
+    /// The most games kept at once; more are refused with 503.
+    pub fn with_max_sessions(mut self, max_sessions: usize) -> Api {
+        self.max_sessions = max_sessions;
+        self
+    }
+
+    /// The language the server logs in. Responses stay in English, since
+    /// clients read them as data.
+    pub fn with_lang(mut self, lang: Lang) -> Api {
+        self.lang = lang;
+        self
+    }
+
+    /// Serves requests until the listener fails, one thread per connection.
+    pub fn run(self, listener: TcpListener) -> io::Result<()> {
+        let api = Arc::new(self);
+
+        for stream in listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(err) => {
+                    eprintln!("{}", api.lang.accept_failed(err));
+                    continue;
+                }
+            };
+
+            let api = Arc::clone(&api);
+            thread::spawn(move || {
+                if let Err(err) = api.handle(stream) {
+                    eprintln!("{}", api.lang.request_failed(err));
+                }
+            });
+        }
+
+        Ok(())
+    }
+
+    /// One request per connection; the response closes it.
+    fn handle(&self, stream: TcpStream) -> io::Result<()> {
+        stream.set_read_timeout(Some(READ_TIMEOUT))?;
+        let mut writer = stream.try_clone()?;
+        let mut reader = BufReader::new(stream);
+
+        let response = match read_request(&mut reader)? {
+            Ok(request) => self.route(&request),
+            Err(response) => response,
+        };
+
+        write_response(&mut writer, &response)
+    }
+
+    fn route(&self, request: &Request) -> Response {
+        let path = request.path.split('?').next().unwrap_or_default();
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+        match (request.method.as_str(), segments.as_slice()) {
+            // CORS preflight, so a page served elsewhere can call the API.
+            ("OPTIONS", _) => Response {
+                status: 204,
+                body: None,
+            },
+            ("POST", ["games"]) => self.create(&request.body),
+            ("GET", ["games", id]) => self.show(id),
+            ("POST", ["games", id, "guesses"]) => self.guess(id, &request.body),
+            (_, ["games"]) | (_, ["games", _]) | (_, ["games", _, "guesses"]) => {
+                Response::error(405, "method not allowed")
+            }
+            _ => Response::error(404, "no such route"),
+        }
+    }
+
+    fn create(&self, body: &[u8]) -> Response {
+        let new: NewGame = if body.iter().all(u8::is_ascii_whitespace) {
+            NewGame::default()
+        } else {
+            match serde_json::from_slice(body) {
+                Ok(new) => new,
+                Err(err) => return Response::error(400, format!("invalid body: {err}")),
+            }
+        };
+
+        let min = new.min.unwrap_or(*self.range.start());
+        let max = new.max.unwrap_or(*self.range.end());
+        if min > max {
+            return Response::error(400, format!("min {min} is greater than max {max}"));
+        }
+        if new.max_attempts == Some(0) {
+            return Response::error(400, "max_attempts must be at least 1");
+        }
+
+        let seed = new.seed.unwrap_or_else(rand::random);
+        let game = Game::with_rng(&mut StdRng::seed_from_u64(seed), min..=max);
+        let game = match new.max_attempts.or(self.max_attempts) {
+            Some(max) => game.with_max_attempts(max),
+            None => game,
+        };
+
+        let mut state = self.lock();
+        if state.sessions.len() >= self.max_sessions {
+            return Response::error(503, "too many games in progress; try again later");
+        }
+        let id = state.next_id;
+        state.next_id += 1;
+
+        let session = Session {
+            game,
+            seed,
+            history: Vec::new(),
+            last_used: Instant::now(),
+        };
+        let body = session.view(id);
+        state.sessions.insert(id, session);
+
+        Response::json(201, body)
+    }
+
+    fn show(&self, id: &str) -> Response {
+        let mut state = self.lock();
+        let (id, session) = match state.session(id) {
+            Ok(found) => found,
+            Err(response) => return response,
+        };
+
+        Response::json(200, session.view(id))
+    }
+
+    fn guess(&self, id: &str, body: &[u8]) -> Response {
+        let input = match serde_json::from_slice::<NewGuess>(body) {
+            Ok(NewGuess {
+                guess: Value::Number(number),
+            }) => number.to_string(),
+            Ok(NewGuess {
+                guess: Value::String(text),
+            }) => text,
+            Ok(_) => return Response::error(400, "guess must be a number or a string"),
+            Err(err) => return Response::error(400, format!("invalid body: {err}")),
+        };
+
+        let mut state = self.lock();
+        let (_, session) = match state.session(id) {
+            Ok(found) => found,
+            Err(response) => return response,
+        };
+
+        if session.game.is_over() {
+            return Response::error(409, "the game is already over");
+        }
+
+        let guess = match session.game.parse(&input) {
+            Ok(guess) => guess,
+            Err(err) => {
+                session.game.reject();
+                return Response::error(422, err.to_string());
+            }
+        };
+
+        let result = Verdict::from(session.game.guess(guess));
+        session.history.push(Turn { guess, result });
+
+        Response::json(
+            200,
+            json!({
+                "result": result,
+                "attempts": session.game.attempts(),
+                "remaining_attempts": session.game.remaining_attempts(),
+                "outcome": session.game.outcome(),
+            }),
+        )
+    }
+
+    /// Also drops every session that has expired since the last request.
+    fn lock(&self) -> MutexGuard<'_, State> {
+        let mut state = self.state.lock().unwrap();
+        let ttl = self.ttl;
+        state
+            .sessions
+            .retain(|_, session| session.last_used.elapsed() < ttl);
+        state
+    }
+}
+
+impl State {
+    /// Looks up a live session and marks it as used.
+    fn session(&mut self, id: &str) -> Result<(u64, &mut Session), Response> {
+        let not_found = || Response::error(404, format!("no game {id:?}; it may have expired"));
+
+        let id: u64 = id.parse().map_err(|_| not_found())?;
+        let session = self.sessions.get_mut(&id).ok_or_else(not_found)?;
+        session.last_used = Instant::now();
+
+        Ok((id, session))
+    }
+}
+
+impl Session {
+    /// The secret only shows up once the game is lost, inside `outcome`.
+    /// The seed would give it away too, so it is held back until the game
+    /// is over.
+    fn view(&self, id: u64) -> Value {
+        let range = self.game.range();
+
+        let mut view = json!({
+            "id": id,
+            "min": range.start(),
+            "max": range.end(),
+            "attempts": self.game.attempts(),
+            "max_attempts": self.game.max_attempts(),
+            "history": self.history,
+            "outcome": self.game.outcome(),
+        });
+        if self.game.is_over() {
+            view["seed"] = json!(self.seed);
+        }
+        view
+    }
+}
+
+/// Reads the request line, the headers and a `Content-Length` body.
+///
+/// The outer error is an I/O failure; the inner one is a response for a
+/// request that could not be understood.
+fn read_request<R: BufRead>(reader: &mut R) -> io::Result<Result<Request, Response>> {
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+
+    let mut parts = line.split_whitespace();
+    let (method, path) = match (parts.next(), parts.next()) {
+        (Some(method), Some(path)) => (method.to_string(), path.to_string()),
+        _ => return Ok(Err(Response::error(400, "malformed request line"))),
+    };
+
+    let mut length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 {
+            break;
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                length = match value.trim().parse() {
+                    Ok(length) => length,
+                    Err(_) => return Ok(Err(Response::error(400, "bad Content-Length"))),
+                };
+            }
+        }
+    }
+
+    if length > MAX_BODY {
+        return Ok(Err(Response::error(413, "request body too large")));
+    }
+
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+
+    Ok(Ok(Request { method, path, body }))
+}
+
+fn write_response<W: Write>(writer: &mut W, response: &Response) -> io::Result<()> {
+    let body = match &response.body {
+        Some(body) => body.to_string(),
+        None => String::new(),
+    };
+
+    write!(
+        writer,
+        "HTTP/1.1 {} {}\r\n\
+         Content-Type: application/json\r\n\
+         Content-Length: {}\r\n\
+         Access-Control-Allow-Origin: *\r\n\
+         Access-Control-Allow-Methods: GET, POST, OPTIONS\r\n\
+         Access-Control-Allow-Headers: Content-Type\r\n\
+         Connection: close\r\n\
+         \r\n\
+         {body}",
+        response.status,
+        reason(response.status),
+        body.len()
+    )?;
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use std::net::SocketAddr;
+
+    /// Starts a server on a free local port and returns its address.
+    fn serve(max_attempts: Option<u32>, ttl: Duration) -> SocketAddr {
+        serve_api(Api::new(1..=100, max_attempts, ttl))
+    }
+
+    fn serve_api(api: Api) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || api.run(listener));
+        addr
+    }
+
+    /// Sends one request and returns the status and the JSON body.
+    fn request(addr: SocketAddr, method: &str, path: &str, body: &str) -> (u16, Value) {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "{method} {path} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        let status = response[9..12].parse().unwrap();
+        let (_, body) = response.split_once("\r\n\r\n").unwrap();
+        let body = if body.is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_str(body).unwrap()
+        };
+        (status, body)
+    }
+
+    fn guess(addr: SocketAddr, id: &Value, guess: u32) -> (u16, Value) {
+        let path = format!("/games/{id}/guesses");
+        request(addr, "POST", &path, &format!("{{\"guess\": {guess}}}"))
+    }
+
+    /// The secret a seeded game on 1 to 100 will have.
+    fn secret(seed: u64) -> u32 {
+        Game::with_rng(&mut StdRng::seed_from_u64(seed), 1..=100).secret()
+    }
+
+    #[test]
+    fn creates_games() {
+        let addr = serve(None, Duration::from_secs(60));
+
+        let (status, game) = request(addr, "POST", "/games", "");
+        assert_eq!(status, 201);
+        assert_eq!(game["min"], 1);
+        assert_eq!(game["max"], 100);
+        assert_eq!(game["attempts"], 0);
+        assert_eq!(game["outcome"], Value::Null);
+
+        let (status, other) = request(
+            addr,
+            "POST",
+            "/games",
+            r#"{"min": 5, "max": 10, "seed": 1, "max_attempts": 3}"#,
+        );
+        assert_eq!(status, 201);
+        assert_ne!(other["id"], game["id"]);
+        assert_eq!((&other["min"], &other["max"]), (&json!(5), &json!(10)));
+        assert_eq!(other["max_attempts"], 3);
+
+        let (status, _) = request(addr, "POST", "/games", r#"{"min": 10, "max": 5}"#);
+        assert_eq!(status, 400);
+        let (status, _) = request(addr, "POST", "/games", r#"{"colour": "red"}"#);
+        assert_eq!(status, 400);
+    }
+
+    #[test]
+    fn scores_guesses_until_the_game_is_won() {
+        let addr = serve(None, Duration::from_secs(60));
+        let (_, game) = request(addr, "POST", "/games", r#"{"seed": 7}"#);
+        let id = &game["id"];
+        let secret = secret(7);
+
+        let (status, body) = guess(addr, id, 0);
+        assert_eq!(status, 422);
+        assert!(body["error"].is_string());
+
+        let (status, body) = request(
+            addr,
+            "POST",
+            &format!("/games/{id}/guesses"),
+            &format!("{{\"guess\": \"{}\"}}", secret + 1),
+        );
+        assert_eq!(status, 200);
+        assert_eq!(body["result"], "too_big");
+        assert_eq!(body["attempts"], 1);
+
+        let (status, body) = guess(addr, id, secret);
+        assert_eq!(status, 200);
+        assert_eq!(body["result"], "correct");
+        assert_eq!(body["outcome"], json!({"result": "won", "attempts": 2}));
+
+        let (status, _) = guess(addr, id, secret);
+        assert_eq!(status, 409);
+    }
+
+    #[test]
+    fn shows_a_game_without_giving_the_secret_away() {
+        let addr = serve(Some(1), Duration::from_secs(60));
+        let (_, game) = request(addr, "POST", "/games", r#"{"seed": 3}"#);
+        let id = &game["id"];
+        assert!(game.get("seed").is_none());
+
+        let wrong = if secret(3) == 1 { 2 } else { 1 };
+        guess(addr, id, wrong);
+
+        let (status, game) = request(addr, "GET", &format!("/games/{id}"), "");
+        assert_eq!(status, 200);
+        assert_eq!(
+            game["history"],
+            json!([{"guess": wrong, "result": "too_small"}])
+        );
+        assert_eq!(
+            game["outcome"],
+            json!({"result": "lost", "attempts": 1, "secret": secret(3)})
+        );
+        assert_eq!(game["seed"], 3);
+
+        let (status, _) = request(addr, "GET", "/games/999", "");
+        assert_eq!(status, 404);
+        let (status, _) = request(addr, "DELETE", &format!("/games/{id}"), "");
+        assert_eq!(status, 405);
+    }
+
+    #[test]
+    fn running_games_hide_the_seed() {
+        let addr = serve(None, Duration::from_secs(60));
+        let (_, game) = request(addr, "POST", "/games", r#"{"seed": 3}"#);
+        guess(addr, &game["id"], if secret(3) == 1 { 2 } else { 1 });
+
+        let (_, game) = request(addr, "GET", &format!("/games/{}", game["id"]), "");
+        assert_eq!(game["attempts"], 1);
+        assert!(game.get("seed").is_none());
+    }
+
+    #[test]
+    fn unused_sessions_expire() {
+        let addr = serve(None, Duration::from_millis(100));
+        let (_, game) = request(addr, "POST", "/games", "");
+        let path = format!("/games/{}", game["id"]);
+
+        let (status, _) = request(addr, "GET", &path, "");
+        assert_eq!(status, 200);
+
+        thread::sleep(Duration::from_millis(300));
+        let (status, body) = request(addr, "GET", &path, "");
+        assert_eq!(status, 404);
+        assert!(body["error"].as_str().unwrap().contains("expired"));
+    }
+
+    #[test]
+    fn refuses_new_games_while_full() {
+        let api = Api::new(1..=100, None, Duration::from_millis(200)).with_max_sessions(2);
+        let addr = serve_api(api);
+
+        let (_, first) = request(addr, "POST", "/games", "");
+        let (status, _) = request(addr, "POST", "/games", "");
+        assert_eq!(status, 201);
+
+        let (status, body) = request(addr, "POST", "/games", "");
+        assert_eq!(status, 503);
+        assert!(body["error"].as_str().unwrap().contains("too many games"));
+
+        // Full only stops new games; the running ones still play.
+        let (status, _) = request(addr, "GET", &format!("/games/{}", first["id"]), "");
+        assert_eq!(status, 200);
+
+        thread::sleep(Duration::from_millis(400));
+        let (status, _) = request(addr, "POST", "/games", "");
+        assert_eq!(status, 201);
+    }
+}
@@ -0,0 +1,612 @@
+use crate::i18n::Lang;
+use crate::numeric::Numeric;
+use rand::Rng;
+use std::env;
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+const DEFAULT_SCORES: &str = "guessing_game_scores.tsv";
+const DEFAULT_BENCH_GAMES: usize = 10_000;
+const DEFAULT_ADDR: &str = "127.0.0.1:7878";
+/// How close a float guess must be when `--tolerance` is not given.
+const DEFAULT_TOLERANCE: f64 = 0.01;
+const DEFAULT_TIME_LIMIT: Duration = Duration::from_secs(60);
+const DEFAULT_GUESS_TIME: Duration = Duration::from_secs(10);
+const DEFAULT_SESSION_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Which kind of secret is being played.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GameMode {
+    #[default]
+    Number,
+    Word,
+    /// The number game against a total and a per-guess clock.
+    TimeAttack,
+}
+
+impl GameMode {
+    pub fn parse(name: &str) -> Option<GameMode> {
+        match name {
+            "number" => Some(GameMode::Number),
+            "word" => Some(GameMode::Word),
+            "time-attack" => Some(GameMode::TimeAttack),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            GameMode::Number => "number",
+            GameMode::Word => "word",
+            GameMode::TimeAttack => "time-attack",
+        }
+    }
+}
+
+/// The Rust type the secret number has in number mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NumberType {
+    U8,
+    U16,
+    #[default]
+    U32,
+    U64,
+    U128,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    F32,
+    F64,
+}
+
+impl NumberType {
+    pub fn parse(name: &str) -> Option<NumberType> {
+        match name {
+            "u8" => Some(NumberType::U8),
+            "u16" => Some(NumberType::U16),
+            "u32" => Some(NumberType::U32),
+            "u64" => Some(NumberType::U64),
+            "u128" => Some(NumberType::U128),
+            "i8" => Some(NumberType::I8),
+            "i16" => Some(NumberType::I16),
+            "i32" => Some(NumberType::I32),
+            "i64" => Some(NumberType::I64),
+            "i128" => Some(NumberType::I128),
+            "f32" => Some(NumberType::F32),
+            "f64" => Some(NumberType::F64),
+            _ => None,
+        }
+    }
+
+    pub fn is_float(self) -> bool {
+        matches!(self, NumberType::F32 | NumberType::F64)
+    }
+}
+
+/// How the secret number's RNG gets seeded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeedMode {
+    Random,
+    Fixed(u64),
+    Daily,
+}
+
+impl SeedMode {
+    /// Picks the concrete seed for this session.
+    pub fn resolve(self) -> u64 {
+        match self {
+            SeedMode::Random => rand::thread_rng().gen(),
+            SeedMode::Fixed(seed) => seed,
+            SeedMode::Daily => daily_seed(SystemTime::now()),
+        }
+    }
+}
+
+/// The seed everyone shares on the (UTC) day containing `now`.
+pub fn daily_seed(now: SystemTime) -> u64 {
+    let seconds = now
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0);
+
+    seconds / SECONDS_PER_DAY
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    pub mode: GameMode,
+    pub range: RangeInclusive<u32>,
+    pub number_type: NumberType,
+    /// `--min` and `--max` as typed, read by `typed_range` for other types.
+    pub bounds: (Option<String>, Option<String>),
+    /// Float types only; `None` means `DEFAULT_TOLERANCE`.
+    pub tolerance: Option<f64>,
+    pub max_attempts: Option<u32>,
+    /// Time attack: the whole game must be won within this.
+    pub time_limit: Duration,
+    /// Time attack: slower guesses count as misses.
+    pub guess_time: Duration,
+    pub seed: SeedMode,
+    pub strict: bool,
+    /// Word mode: revealed letters must be reused.
+    pub hard: bool,
+    pub hints: bool,
+    /// Draw the full-screen interface instead of plain lines.
+    pub tui: bool,
+    /// `--lang`; `None` follows the environment.
+    pub lang: Option<Lang>,
+    pub player: Option<String>,
+    pub scores: PathBuf,
+    /// How many games `bench` simulates per strategy.
+    pub games: usize,
+    /// Where `serve` and `api` listen.
+    pub addr: String,
+    /// How long `api` keeps an idle game around.
+    pub session_ttl: Duration,
+    /// Where to write a JSON Lines recording of the session.
+    pub record: Option<PathBuf>,
+    /// Read guesses from this file instead of standard input.
+    pub input: Option<PathBuf>,
+    /// Print one JSON report at the end instead of talking to the player.
+    pub json: bool,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            mode: GameMode::Number,
+            range: 1..=100,
+            number_type: NumberType::U32,
+            bounds: (None, None),
+            tolerance: None,
+            max_attempts: None,
+            time_limit: DEFAULT_TIME_LIMIT,
+            guess_time: DEFAULT_GUESS_TIME,
+            seed: SeedMode::Random,
+            strict: false,
+            hard: false,
+            hints: false,
+            tui: false,
+            lang: None,
+            player: None,
+            scores: PathBuf::from(DEFAULT_SCORES),
+            games: DEFAULT_BENCH_GAMES,
+            addr: String::from(DEFAULT_ADDR),
+            session_ttl: DEFAULT_SESSION_TTL,
+            record: None,
+            input: None,
+            json: false,
+        }
+    }
+}
+
+/// What the binary was asked to do.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Play(Config),
+    Stats(Config),
+    Leaderboard(Config),
+    Reverse(Config),
+    Bench(Config),
+    Serve(Config),
+    Api(Config),
+    Replay(PathBuf),
+}
+
+impl Command {
+    /// Parses the full argument list, including the program name.
+    ///
+    /// Without a subcommand the game is played, so the old
+    /// `guessing_game --preset hard` style keeps working.
+    pub fn build(args: impl Iterator<Item = String>) -> Result<Command, String> {
+        let mut args = args.skip(1).peekable();
+
+        let command = match args.peek().map(String::as_str) {
+            Some("play") => Command::Play,
+            Some("stats") => Command::Stats,
+            Some("leaderboard") => Command::Leaderboard,
+            Some("reverse") => Command::Reverse,
+            Some("bench") => Command::Bench,
+            Some("serve") => Command::Serve,
+            Some("api") => Command::Api,
+            Some("replay") => {
+                args.next();
+                let log = args.next().ok_or("replay expects the path of a log")?;
+                if let Some(arg) = args.next() {
+                    return Err(format!("unknown argument: {arg}"));
+                }
+                return Ok(Command::Replay(PathBuf::from(log)));
+            }
+            _ => return Ok(Command::Play(Config::build(args)?)),
+        };
+        args.next();
+
+        Ok(command(Config::build(args)?))
+    }
+}
+
+impl Config {
+    /// Looks up one of the named difficulty presets.
+    pub fn preset(name: &str) -> Option<Config> {
+        let (range, max_attempts) = match name {
+            "easy" => (1..=10, 5),
+            "normal" => (1..=100, 10),
+            "hard" => (1..=1000, 10),
+            _ => return None,
+        };
+
+        Some(Config {
+            range,
+            max_attempts: Some(max_attempts),
+            ..Config::default()
+        })
+    }
+
+    /// Builds a config from the options that follow the subcommand.
+    ///
+    /// A `--preset` is applied first, so `--min`, `--max` and `--attempts`
+    /// override it no matter where they appear.
+    pub fn build(mut args: impl Iterator<Item = String>) -> Result<Config, String> {
+        let mut mode = GameMode::Number;
+        let mut number_type = NumberType::U32;
+        let mut tolerance = None;
+        let mut preset = None;
+        let mut min = None;
+        let mut max = None;
+        let mut max_attempts = None;
+        let mut time_limit = None;
+        let mut guess_time = None;
+        let mut seed = SeedMode::Random;
+        let mut strict = false;
+        let mut hard = false;
+        let mut hints = false;
+        let mut tui = false;
+        let mut lang = None;
+        let mut player = None;
+        let mut scores = None;
+        let mut games = None;
+        let mut addr = None;
+        let mut session_ttl = None;
+        let mut record = None;
+        let mut input = None;
+        let mut json = false;
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("missing value for {arg}"))
+            };
+
+            match arg.as_str() {
+                "--mode" => {
+                    let name = value()?;
+                    mode = GameMode::parse(&name).ok_or(format!("unknown mode: {name}"))?;
+                }
+                "--type" => {
+                    let name = value()?;
+                    number_type =
+                        NumberType::parse(&name).ok_or(format!("unknown number type: {name}"))?;
+                }
+                "--tolerance" => {
+                    let value: f64 = parse_number(&arg, &value()?)?;
+                    if !value.is_finite() || value < 0.0 {
+                        return Err(format!("{arg} expects a non-negative number, got {value}"));
+                    }
+                    tolerance = Some(value);
+                }
+                "--preset" => preset = Some(value()?),
+                "--min" => min = Some(value()?),
+                "--max" => max = Some(value()?),
+                "--attempts" => max_attempts = Some(parse_number(&arg, &value()?)?),
+                "--time-limit" => time_limit = Some(parse_seconds(&arg, &value()?)?),
+                "--guess-time" => guess_time = Some(parse_seconds(&arg, &value()?)?),
+                "--seed" => seed = SeedMode::Fixed(parse_number(&arg, &value()?)?),
+                "--daily" => seed = SeedMode::Daily,
+                "--strict" => strict = true,
+                "--hard" => hard = true,
+                "--hints" => hints = true,
+                "--tui" => tui = true,
+                "--lang" => {
+                    let name = value()?;
+                    lang = Some(Lang::parse(&name).ok_or(format!("unknown language: {name}"))?);
+                }
+                "--name" => player = Some(value()?),
+                "--scores" => scores = Some(PathBuf::from(value()?)),
+                "--games" => games = Some(parse_number(&arg, &value()?)?),
+                "--addr" => addr = Some(value()?),
+                "--session-ttl" => session_ttl = Some(parse_seconds(&arg, &value()?)?),
+                "--record" => record = Some(PathBuf::from(value()?)),
+                "--input" => input = Some(PathBuf::from(value()?)),
+                "--json" => json = true,
+                _ => return Err(format!("unknown argument: {arg}")),
+            }
+        }
+
+        let mut config = match preset {
+            Some(name) => Config::preset(&name).ok_or(format!("unknown preset: {name}"))?,
+            None => Config::default(),
+        };
+
+        // Other types are checked by `typed_range` once the type is known.
+        if number_type == NumberType::U32 {
+            let min = match &min {
+                Some(min) => parse_number("--min", min)?,
+                None => *config.range.start(),
+            };
+            let max = match &max {
+                Some(max) => parse_number("--max", max)?,
+                None => *config.range.end(),
+            };
+            if min > max {
+                return Err(format!("--min {min} is greater than --max {max}"));
+            }
+            config.range = min..=max;
+        }
+        config.number_type = number_type;
+        config.bounds = (min, max);
+
+        if tolerance.is_some() && !number_type.is_float() {
+            return Err(String::from(
+                "--tolerance only applies to --type f32 and f64",
+            ));
+        }
+        config.tolerance = tolerance;
+
+        if let Some(attempts) = max_attempts {
+            if attempts == 0 {
+                return Err(String::from("--attempts must be at least 1"));
+            }
+            config.max_attempts = Some(attempts);
+        }
+
+        if let Some(time_limit) = time_limit {
+            config.time_limit = time_limit;
+        }
+        if let Some(guess_time) = guess_time {
+            config.guess_time = guess_time;
+        }
+
+        config.seed = seed;
+        config.mode = mode;
+        config.strict = strict;
+        config.hard = hard;
+        config.hints = hints;
+        config.tui = tui;
+        config.lang = lang;
+        config.player = player;
+        if let Some(scores) = scores {
+            config.scores = scores;
+        }
+        if let Some(games) = games {
+            config.games = games;
+        }
+        if let Some(addr) = addr {
+            config.addr = addr;
+        }
+        if let Some(session_ttl) = session_ttl {
+            config.session_ttl = session_ttl;
+        }
+        config.record = record;
+        config.input = input;
+        config.json = json;
+
+        if mode != GameMode::Number && (config.tui || config.record.is_some()) {
+            return Err(String::from("--tui and --record only work in number mode"));
+        }
+        if json && (mode == GameMode::Word || config.tui) {
+            return Err(String::from(
+                "--json only works in the number modes, without --tui",
+            ));
+        }
+        if number_type != NumberType::U32
+            && (mode != GameMode::Number || config.tui || config.record.is_some())
+        {
+            return Err(String::from(
+                "--type only works in number mode, without --tui or --record",
+            ));
+        }
+
+        Ok(config)
+    }
+
+    /// The range for a secret of type `T`: `--min` and `--max` parsed as
+    /// `T`, falling back to the preset's bounds.
+    pub fn typed_range<T: Numeric>(&self) -> Result<RangeInclusive<T>, String> {
+        let bound = |arg: &str, text: &Option<String>, default: u32| {
+            let text = text.clone().unwrap_or_else(|| default.to_string());
+            T::parse_number(text.trim()).map_err(|err| format!("{arg} for {}: {err}", T::NAME))
+        };
+
+        let min = bound("--min", &self.bounds.0, *self.range.start())?;
+        let max = bound("--max", &self.bounds.1, *self.range.end())?;
+        if min > max {
+            return Err(format!("--min {min} is greater than --max {max}"));
+        }
+        if !T::span_is_finite(min, max) {
+            return Err(format!(
+                "--min {} to --max {} is too wide a range for {}",
+                min.bound_text(),
+                max.bound_text(),
+                T::NAME
+            ));
+        }
+
+        Ok(min..=max)
+    }
+
+    /// `--tolerance`, or the default for float types.
+    pub fn tolerance(&self) -> f64 {
+        self.tolerance.unwrap_or(DEFAULT_TOLERANCE)
+    }
+
+    /// `--lang` if given, otherwise whatever `LANG` asks for.
+    pub fn lang(&self) -> Lang {
+        self.lang.unwrap_or_else(Lang::from_env)
+    }
+
+    /// The name games are recorded under: `--name`, else `$USER`.
+    pub fn player_name(&self) -> String {
+        self.player
+            .clone()
+            .or_else(|| env::var("USER").ok())
+            .unwrap_or_else(|| String::from("anonymous"))
+    }
+}
+
+fn parse_number<T: FromStr>(arg: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{arg} expects a non-negative number, got {value:?}"))
+}
+
+/// Whole seconds, at least one.
+fn parse_seconds(arg: &str, value: &str) -> Result<Duration, String> {
+    match value.parse() {
+        Ok(seconds) if seconds > 0 => Ok(Duration::from_secs(seconds)),
+        _ => Err(format!("{arg} expects a number of seconds, got {value:?}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Game;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn config(args: &str) -> Config {
+        Config::build(args.split_whitespace().map(String::from)).unwrap()
+    }
+
+    fn error(args: &str) -> String {
+        Config::build(args.split_whitespace().map(String::from)).unwrap_err()
+    }
+
+    #[test]
+    fn presets_set_the_range_and_attempts() {
+        for (name, range, attempts) in [
+            ("easy", 1..=10, 5),
+            ("normal", 1..=100, 10),
+            ("hard", 1..=1000, 10),
+        ] {
+            let preset = config(&format!("--preset {name}"));
+            assert_eq!(preset.range, range, "{name}");
+            assert_eq!(preset.max_attempts, Some(attempts), "{name}");
+            assert_eq!(Config::preset(name), Some(preset));
+        }
+
+        assert_eq!(Config::preset("extreme"), None);
+        assert_eq!(error("--preset extreme"), "unknown preset: extreme");
+    }
+
+    #[test]
+    fn options_override_the_preset_wherever_they_appear() {
+        let before = config("--max 50 --attempts 3 --preset hard");
+        assert_eq!(before.range, 1..=50);
+        assert_eq!(before.max_attempts, Some(3));
+
+        let after = config("--preset easy --min 5 --attempts 8");
+        assert_eq!(after.range, 5..=10);
+        assert_eq!(after.max_attempts, Some(8));
+
+        assert_eq!(
+            error("--preset easy --min 20"),
+            "--min 20 is greater than --max 10"
+        );
+    }
+
+    #[test]
+    fn attempts_must_be_at_least_one() {
+        assert_eq!(error("--attempts 0"), "--attempts must be at least 1");
+        assert_eq!(
+            error("--preset normal --attempts 0"),
+            "--attempts must be at least 1"
+        );
+        assert!(error("--attempts -1").starts_with("--attempts expects"));
+        assert_eq!(config("--attempts 1").max_attempts, Some(1));
+        assert_eq!(config("").max_attempts, None);
+    }
+
+    fn day(days: u64, seconds: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(days * SECONDS_PER_DAY + seconds)
+    }
+
+    fn daily_secret(now: SystemTime, preset: &str) -> u32 {
+        let config = Config::preset(preset).unwrap();
+        let mut rng = StdRng::seed_from_u64(daily_seed(now));
+        Game::from_config(&mut rng, &config).secret()
+    }
+
+    #[test]
+    fn daily_seeds_follow_the_utc_date() {
+        let morning = day(20_000, 60);
+        let evening = day(20_000, SECONDS_PER_DAY - 1);
+        assert_eq!(daily_seed(morning), 20_000);
+        assert_eq!(daily_seed(morning), daily_seed(evening));
+        assert_eq!(daily_secret(morning, "hard"), daily_secret(evening, "hard"));
+
+        let next = day(20_001, 0);
+        assert_ne!(daily_seed(next), daily_seed(evening));
+        assert_ne!(daily_secret(next, "hard"), daily_secret(evening, "hard"));
+
+        assert_eq!(daily_seed(UNIX_EPOCH - Duration::from_secs(1)), 0);
+    }
+
+    #[test]
+    fn presets_share_the_daily_seed_but_not_the_secret() {
+        let today = day(20_000, 0);
+        let secrets: Vec<u32> = ["easy", "normal", "hard"]
+            .into_iter()
+            .map(|preset| daily_secret(today, preset))
+            .collect();
+
+        assert!((1..=10).contains(&secrets[0]), "{secrets:?}");
+        assert_ne!(secrets[0], secrets[1], "{secrets:?}");
+        assert_ne!(secrets[1], secrets[2], "{secrets:?}");
+        assert_ne!(secrets[0], secrets[2], "{secrets:?}");
+    }
+
+    #[test]
+    fn typed_ranges_parse_the_bounds_as_the_type() {
+        assert_eq!(
+            config("--type i8 --min -5 --max 5").typed_range::<i8>(),
+            Ok(-5..=5)
+        );
+        assert_eq!(
+            config("--type f64 --min 0.5 --max 2.5").typed_range::<f64>(),
+            Ok(0.5..=2.5)
+        );
+        assert!(config("--type u8 --min 1 --max 300")
+            .typed_range::<u8>()
+            .unwrap_err()
+            .starts_with("--max for u8"));
+    }
+
+    #[test]
+    fn float_ranges_must_have_a_finite_width() {
+        let err = config("--type f64 --min -1e308 --max 1e308")
+            .typed_range::<f64>()
+            .unwrap_err();
+        assert_eq!(
+            err,
+            "--min -1e308 to --max 1e308 is too wide a range for f64"
+        );
+
+        let err = config("--type f32 --min -3e38 --max 3e38")
+            .typed_range::<f32>()
+            .unwrap_err();
+        assert!(err.ends_with("too wide a range for f32"), "{err}");
+
+        assert!(config("--type f64 --min -1e307 --max 1e307")
+            .typed_range::<f64>()
+            .is_ok());
+        assert!(config("--type i128 --min -1e308 --max 1e308")
+            .typed_range::<i128>()
+            .is_err());
+    }
+}
@@ -0,0 +1,282 @@
+use crate::numeric::Numeric;
+use std::error::Error;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+/// Why a line of input was not accepted as a guess of type `T`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuessError<T = u32> {
+    Empty,
+    NotANumber(String),
+    /// A negative number, for a type that cannot hold one.
+    Negative(String),
+    Overflow(String),
+    Underflow(String),
+    OutOfRange {
+        guess: T,
+        min: T,
+        max: T,
+    },
+}
+
+impl<T: Numeric> fmt::Display for GuessError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GuessError::Empty => write!(f, "Please type a number."),
+            GuessError::NotANumber(input) => write!(f, "{input:?} is not a number."),
+            GuessError::Negative(input) => {
+                write!(f, "{input} is negative; the secret is never below zero.")
+            }
+            GuessError::Overflow(input) => {
+                write!(
+                    f,
+                    "{input} is too large; guesses go up to {}.",
+                    T::MAX.bound_text()
+                )
+            }
+            GuessError::Underflow(input) => {
+                write!(
+                    f,
+                    "{input} is too small; guesses go down to {}.",
+                    T::MIN.bound_text()
+                )
+            }
+            GuessError::OutOfRange { guess, min, max } => {
+                write!(f, "{guess} is outside the range {min} to {max}.")
+            }
+        }
+    }
+}
+
+impl<T: Numeric> Error for GuessError<T> {}
+
+/// Parses one line of input into a guess inside `range`.
+pub fn parse_guess<T: Numeric>(input: &str, range: &RangeInclusive<T>) -> Result<T, GuessError<T>> {
+    let input = input.trim();
+
+    if input.is_empty() {
+        return Err(GuessError::Empty);
+    }
+
+    let guess = T::parse_number(input)?;
+
+    if !range.contains(&guess) {
+        return Err(GuessError::OutOfRange {
+            guess,
+            min: *range.start(),
+            max: *range.end(),
+        });
+    }
+
+    Ok(guess)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{play, Game, GuessSource, Hint, Outcome, Reporter};
+    use std::cmp::Ordering;
+
+    fn error<T: Numeric>(input: &str, range: RangeInclusive<T>) -> (GuessError<T>, String) {
+        let err = parse_guess(input, &range).unwrap_err();
+        let message = err.to_string();
+        (err, message)
+    }
+
+    #[test]
+    fn accepts_numbers_in_range() {
+        assert_eq!(parse_guess(" 42\n", &(1..=100)), Ok(42));
+        assert_eq!(parse_guess("1", &(1..=100)), Ok(1));
+        assert_eq!(parse_guess("100", &(1..=100)), Ok(100));
+        assert_eq!(parse_guess("-3", &(-5..=5)), Ok(-3));
+        assert_eq!(parse_guess("0.5", &(0.0..=1.0)), Ok(0.5));
+    }
+
+    #[test]
+    fn rejects_empty_input() {
+        assert_eq!(
+            error("  \n", 1..=100u32),
+            (GuessError::Empty, String::from("Please type a number."))
+        );
+    }
+
+    #[test]
+    fn rejects_words() {
+        assert_eq!(
+            error("fifty", 1..=100u32),
+            (
+                GuessError::NotANumber(String::from("fifty")),
+                String::from("\"fifty\" is not a number.")
+            )
+        );
+        assert_eq!(
+            error("NaN", 0.0..=1.0f64).0,
+            GuessError::NotANumber(String::from("NaN"))
+        );
+    }
+
+    #[test]
+    fn rejects_negative_numbers_for_unsigned_types() {
+        assert_eq!(
+            error("-7", 1..=100u32),
+            (
+                GuessError::Negative(String::from("-7")),
+                String::from("-7 is negative; the secret is never below zero.")
+            )
+        );
+        // A minus sign on its own is just not a number.
+        assert_eq!(
+            error("-", 1..=100u32).0,
+            GuessError::NotANumber(String::from("-"))
+        );
+    }
+
+    #[test]
+    fn rejects_numbers_too_large_for_the_type() {
+        assert_eq!(
+            error("4294967296", 1..=100u32),
+            (
+                GuessError::Overflow(String::from("4294967296")),
+                String::from("4294967296 is too large; guesses go up to 4294967295.")
+            )
+        );
+        assert_eq!(
+            error("1e39", 0.0..=1.0f32),
+            (
+                GuessError::Overflow(String::from("1e39")),
+                String::from("1e39 is too large; guesses go up to 3.4028235e38.")
+            )
+        );
+    }
+
+    #[test]
+    fn rejects_numbers_too_small_for_the_type() {
+        assert_eq!(
+            error("-129", -10..=10i8),
+            (
+                GuessError::Underflow(String::from("-129")),
+                String::from("-129 is too small; guesses go down to -128.")
+            )
+        );
+    }
+
+    #[test]
+    fn rejects_numbers_outside_the_range() {
+        assert_eq!(
+            error("101", 1..=100u32),
+            (
+                GuessError::OutOfRange {
+                    guess: 101,
+                    min: 1,
+                    max: 100
+                },
+                String::from("101 is outside the range 1 to 100.")
+            )
+        );
+        assert!(matches!(
+            error("0", 1..=100u32).0,
+            GuessError::OutOfRange { guess: 0, .. }
+        ));
+    }
+
+    /// Lines to play, then nothing.
+    struct Lines(std::vec::IntoIter<&'static str>);
+
+    impl GuessSource for Lines {
+        fn next_guess(&mut self) -> Option<String> {
+            self.0.next().map(String::from)
+        }
+    }
+
+    /// Keeps the attempts used after each rejected line.
+    #[derive(Default)]
+    struct Rejections {
+        seen: Vec<(GuessError, u32)>,
+        pending: Option<GuessError>,
+    }
+
+    impl Reporter for Rejections {
+        fn start(&mut self) {}
+
+        fn prompt(&mut self) {}
+
+        fn invalid(&mut self, error: &GuessError) {
+            self.pending = Some(error.clone());
+        }
+
+        fn guessed(&mut self, _guess: u32) {}
+
+        fn feedback(&mut self, _ordering: Ordering) {}
+
+        fn attempts(&mut self, used: u32) {
+            if let Some(error) = self.pending.take() {
+                self.seen.push((error, used));
+            }
+        }
+
+        fn hint(&mut self, _hint: &Hint) {}
+
+        fn finish(&mut self, _outcome: &Outcome) {}
+    }
+
+    const REJECTED: [&str; 5] = ["", "abc", "-1", "99999999999", "11"];
+
+    #[test]
+    fn strict_games_charge_every_kind_of_rejected_input() {
+        let mut game = Game::new(5).with_range(1..=10).with_strict(true);
+        let mut reporter = Rejections::default();
+
+        let outcome = play(
+            &mut game,
+            &mut Lines(REJECTED.to_vec().into_iter()),
+            &mut reporter,
+        );
+
+        assert_eq!(outcome, Outcome::Quit { attempts: 5 });
+        let used: Vec<u32> = reporter.seen.iter().map(|(_, used)| *used).collect();
+        assert_eq!(used, [1, 2, 3, 4, 5]);
+        assert!(matches!(reporter.seen[0].0, GuessError::Empty));
+        assert!(matches!(reporter.seen[1].0, GuessError::NotANumber(_)));
+        assert!(matches!(reporter.seen[2].0, GuessError::Negative(_)));
+        assert!(matches!(reporter.seen[3].0, GuessError::Overflow(_)));
+        assert!(matches!(reporter.seen[4].0, GuessError::OutOfRange { .. }));
+    }
+
+    #[test]
+    fn relaxed_games_charge_nothing_for_rejected_input() {
+        let mut game = Game::new(5).with_range(1..=10);
+        let mut reporter = Rejections::default();
+
+        let outcome = play(
+            &mut game,
+            &mut Lines(REJECTED.to_vec().into_iter()),
+            &mut reporter,
+        );
+
+        assert_eq!(outcome, Outcome::Quit { attempts: 0 });
+        assert_eq!(reporter.seen.len(), REJECTED.len());
+        assert!(reporter.seen.iter().all(|(_, used)| *used == 0));
+    }
+
+    #[test]
+    fn strict_games_can_be_lost_on_rejected_input() {
+        let mut game = Game::new(5)
+            .with_range(1..=10)
+            .with_strict(true)
+            .with_max_attempts(3);
+
+        let outcome = play(
+            &mut game,
+            &mut Lines(REJECTED.to_vec().into_iter()),
+            &mut Rejections::default(),
+        );
+
+        assert_eq!(
+            outcome,
+            Outcome::Lost {
+                attempts: 3,
+                secret: 5
+            }
+        );
+    }
+}
@@ -0,0 +1,205 @@
+use crate::numeric::Numeric;
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+/// How close a guess is, relative to the size of the range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Proximity {
+    Burning,
+    Hot,
+    Warm,
+    Cold,
+}
+
+impl fmt::Display for Proximity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let word = match self {
+            Proximity::Burning => "Burning!",
+            Proximity::Hot => "Hot!",
+            Proximity::Warm => "Warm.",
+            Proximity::Cold => "Cold.",
+        };
+
+        write!(f, "{word}")
+    }
+}
+
+/// How the latest guess compares with the one before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trend {
+    Closer,
+    Further,
+    Same,
+}
+
+impl fmt::Display for Trend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let words = match self {
+            Trend::Closer => "Getting warmer.",
+            Trend::Further => "Getting colder.",
+            Trend::Same => "No closer than before.",
+        };
+
+        write!(f, "{words}")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hint {
+    pub proximity: Proximity,
+    /// `None` for the first guess, which has nothing to compare with.
+    pub trend: Option<Trend>,
+}
+
+impl fmt::Display for Hint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.proximity)?;
+        if let Some(trend) = self.trend {
+            write!(f, " {trend}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Turns distances into hints, remembering the previous guess.
+#[derive(Debug, Clone)]
+pub struct Hinter {
+    /// Largest distance still counted as burning, hot and warm.
+    thresholds: [f64; 3],
+    previous: Option<f64>,
+}
+
+impl Hinter {
+    /// Burning is within 2% of the range, hot 10% and warm 25%.
+    ///
+    /// For integers the thresholds round up to whole steps, each at least
+    /// one beyond the last, so every hint still occurs on `1..=10`.
+    pub fn new<T: Numeric>(range: &RangeInclusive<T>) -> Hinter {
+        let span = range.start().distance(*range.end());
+        let mut thresholds = [2.0, 10.0, 25.0].map(|percent| span * percent / 100.0);
+
+        if !T::IS_FLOAT {
+            let mut previous = 0.0;
+            for threshold in &mut thresholds {
+                *threshold = threshold.ceil().max(previous + 1.0);
+                previous = *threshold;
+            }
+        }
+
+        Hinter {
+            thresholds,
+            previous: None,
+        }
+    }
+
+    pub fn hint<T: Numeric>(&mut self, guess: T, secret: T) -> Hint {
+        let distance = guess.distance(secret);
+
+        let proximity = match self.thresholds.iter().position(|&max| distance <= max) {
+            Some(0) => Proximity::Burning,
+            Some(1) => Proximity::Hot,
+            Some(_) => Proximity::Warm,
+            None => Proximity::Cold,
+        };
+
+        let trend = self
+            .previous
+            .map(|previous| match distance.total_cmp(&previous) {
+                Ordering::Less => Trend::Closer,
+                Ordering::Greater => Trend::Further,
+                Ordering::Equal => Trend::Same,
+            });
+        self.previous = Some(distance);
+
+        Hint { proximity, trend }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Config;
+    use std::collections::BTreeSet;
+
+    /// Every hint a guess anywhere in the preset's range can get.
+    fn proximities(preset: &str, secret: u32) -> BTreeSet<Proximity> {
+        let range = Config::preset(preset).unwrap().range;
+        range
+            .clone()
+            .filter(|&guess| guess != secret)
+            .map(|guess| Hinter::new(&range).hint(guess, secret).proximity)
+            .collect()
+    }
+
+    #[test]
+    fn every_hint_occurs_on_every_preset() {
+        let all = BTreeSet::from([
+            Proximity::Burning,
+            Proximity::Hot,
+            Proximity::Warm,
+            Proximity::Cold,
+        ]);
+
+        for preset in ["easy", "normal", "hard"] {
+            assert_eq!(proximities(preset, 1), all, "{preset}");
+        }
+        assert_eq!(proximities("easy", 5).len(), 4);
+    }
+
+    #[test]
+    fn small_integer_ranges_get_one_step_per_hint() {
+        let mut hinter = Hinter::new(&(1..=10));
+        let hints: Vec<Proximity> = (2..=5)
+            .map(|guess| hinter.hint(guess, 1).proximity)
+            .collect();
+
+        assert_eq!(
+            hints,
+            [
+                Proximity::Burning,
+                Proximity::Hot,
+                Proximity::Warm,
+                Proximity::Cold
+            ]
+        );
+        assert_eq!(Hinter::new(&(1..=100)).thresholds, [2.0, 10.0, 25.0]);
+        assert_eq!(Hinter::new(&(1..=1000)).thresholds, [20.0, 100.0, 250.0]);
+    }
+
+    #[test]
+    fn float_thresholds_stay_proportional() {
+        let hinter = Hinter::new(&(0.0..=1.0));
+        assert_eq!(hinter.thresholds, [0.02, 0.1, 0.25]);
+    }
+
+    #[test]
+    fn trends_compare_with_the_previous_guess() {
+        let mut hinter = Hinter::new(&(1..=100));
+        let trends: Vec<Option<Trend>> = [90, 70, 70, 80, 51]
+            .into_iter()
+            .map(|guess| hinter.hint(guess, 50).trend)
+            .collect();
+
+        assert_eq!(
+            trends,
+            [
+                None,
+                Some(Trend::Closer),
+                Some(Trend::Same),
+                Some(Trend::Further),
+                Some(Trend::Closer)
+            ]
+        );
+        // The same distance on the other side of the secret is no closer.
+        assert_eq!(hinter.hint(49, 50).trend, Some(Trend::Same));
+    }
+
+    #[test]
+    fn hints_read_as_a_sentence() {
+        let mut hinter = Hinter::new(&(1..=100));
+        assert_eq!(hinter.hint(60, 50).to_string(), "Hot!");
+        assert_eq!(hinter.hint(51, 50).to_string(), "Burning! Getting warmer.");
+        assert_eq!(hinter.hint(99, 50).to_string(), "Cold. Getting colder.");
+    }
+}
@@ -0,0 +1,720 @@
+use crate::hint::{Proximity, Trend};
+use crate::numeric::Numeric;
+use crate::reverse::{Answer, Cheating};
+use crate::word::WordError;
+use crate::{GuessError, Hint};
+use std::env;
+use std::fmt::{Debug, Display};
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+/// The languages the game can talk in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lang {
+    En,
+    Ko,
+}
+
+impl Lang {
+    /// Accepts `en`/`ko` and the `kr` code the book itself uses.
+    pub fn parse(name: &str) -> Option<Lang> {
+        match name.to_lowercase().as_str() {
+            "en" | "english" => Some(Lang::En),
+            "ko" | "kr" | "korean" | "한국어" => Some(Lang::Ko),
+            _ => None,
+        }
+    }
+
+    /// Reads `LC_ALL`, `LC_MESSAGES` and `LANG` in the usual order, so
+    /// `LANG=ko_KR.UTF-8` picks Korean. Anything else falls back to English.
+    pub fn from_env() -> Lang {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .map(|value| {
+                if value.to_lowercase().starts_with("ko") {
+                    Lang::Ko
+                } else {
+                    Lang::En
+                }
+            })
+            .unwrap_or(Lang::En)
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Lang::En => "Guess the number!",
+            Lang::Ko => "숫자를 맞혀 보세요!",
+        }
+    }
+
+    pub fn prompt(self) -> &'static str {
+        match self {
+            Lang::En => "Please input your guess.",
+            Lang::Ko => "추측한 숫자를 입력하세요.",
+        }
+    }
+
+    pub fn guessed<T: Display>(self, guess: T) -> String {
+        match self {
+            Lang::En => format!("You guessed: {guess}"),
+            Lang::Ko => format!("입력한 숫자: {guess}"),
+        }
+    }
+
+    pub fn too_small(self) -> &'static str {
+        match self {
+            Lang::En => "Too small!",
+            Lang::Ko => "너무 작습니다!",
+        }
+    }
+
+    pub fn too_big(self) -> &'static str {
+        match self {
+            Lang::En => "Too big!",
+            Lang::Ko => "너무 큽니다!",
+        }
+    }
+
+    pub fn correct(self) -> &'static str {
+        match self {
+            Lang::En => "Correct!",
+            Lang::Ko => "정답!",
+        }
+    }
+
+    /// "1 attempt", "2 attempts"; Korean counters have no plural.
+    pub fn attempts(self, count: u32) -> String {
+        match self {
+            Lang::En => plural(count.into(), "attempt", "attempts"),
+            Lang::Ko => format!("{count}번"),
+        }
+    }
+
+    /// "1 guess", "2 guesses", as `attempts` does.
+    pub fn guesses(self, count: u32) -> String {
+        match self {
+            Lang::En => plural(count.into(), "guess", "guesses"),
+            Lang::Ko => format!("{count}번"),
+        }
+    }
+
+    /// "1 game", "2 games", as `attempts` does.
+    pub fn games(self, count: usize) -> String {
+        match self {
+            Lang::En => plural(count as u64, "game", "games"),
+            Lang::Ko => format!("{count}게임"),
+        }
+    }
+
+    pub fn win(self, attempts: u32) -> String {
+        match self {
+            Lang::En => format!("You win in {}!", self.attempts(attempts)),
+            Lang::Ko => format!("{} 만에 맞혔습니다!", self.attempts(attempts)),
+        }
+    }
+
+    pub fn lose<T: Display>(self, secret: T) -> String {
+        match self {
+            Lang::En => format!("You lose, the number was {secret}."),
+            Lang::Ko => format!("졌습니다. 정답은 {secret}입니다."),
+        }
+    }
+
+    pub fn bye(self) -> &'static str {
+        match self {
+            Lang::En => "Bye!",
+            Lang::Ko => "다음에 또 만나요!",
+        }
+    }
+
+    pub fn seed(self, seed: u64) -> String {
+        match self {
+            Lang::En => format!("Seed: {seed} (replay with --seed {seed})"),
+            Lang::Ko => {
+                format!("시드: {seed} (--seed {seed} 옵션으로 같은 게임을 다시 할 수 있습니다)")
+            }
+        }
+    }
+
+    pub fn attempts_label(self) -> &'static str {
+        match self {
+            Lang::En => "Attempts",
+            Lang::Ko => "시도",
+        }
+    }
+
+    pub fn time_label(self) -> &'static str {
+        match self {
+            Lang::En => "Time",
+            Lang::Ko => "시간",
+        }
+    }
+
+    pub fn history_label(self) -> &'static str {
+        match self {
+            Lang::En => "History",
+            Lang::Ko => "기록",
+        }
+    }
+
+    pub fn candidates(self, low: u32, high: u32) -> String {
+        match self {
+            Lang::En => format!("Somewhere from {low} to {high}"),
+            Lang::Ko => format!("{low}부터 {high} 사이"),
+        }
+    }
+
+    pub fn earlier(self, count: usize) -> String {
+        match self {
+            Lang::En => format!("… {count} earlier"),
+            Lang::Ko => format!("… 이전 {count}개"),
+        }
+    }
+
+    pub fn guess_error<T: Numeric>(self, error: &GuessError<T>) -> String {
+        match self {
+            Lang::En => error.to_string(),
+            Lang::Ko => match error {
+                GuessError::Empty => String::from("숫자를 입력하세요."),
+                GuessError::NotANumber(input) => format!("{input:?}은(는) 숫자가 아닙니다."),
+                GuessError::Negative(input) => {
+                    format!("{input}은(는) 음수입니다. 비밀 숫자는 0보다 작지 않습니다.")
+                }
+                GuessError::Overflow(input) => {
+                    format!(
+                        "{input}은(는) 너무 큽니다. {}까지 입력할 수 있습니다.",
+                        T::MAX.bound_text()
+                    )
+                }
+                GuessError::Underflow(input) => {
+                    format!(
+                        "{input}은(는) 너무 작습니다. {}부터 입력할 수 있습니다.",
+                        T::MIN.bound_text()
+                    )
+                }
+                GuessError::OutOfRange { guess, min, max } => {
+                    format!("{guess}은(는) {min}부터 {max} 사이의 숫자가 아닙니다.")
+                }
+            },
+        }
+    }
+
+    pub fn hint(self, hint: &Hint) -> String {
+        if self == Lang::En {
+            return hint.to_string();
+        }
+
+        let proximity = match hint.proximity {
+            Proximity::Burning => "아주 뜨거워요!",
+            Proximity::Hot => "뜨거워요!",
+            Proximity::Warm => "따뜻해요.",
+            Proximity::Cold => "차가워요.",
+        };
+        let trend = match hint.trend {
+            Some(Trend::Closer) => " 점점 가까워지고 있어요.",
+            Some(Trend::Further) => " 점점 멀어지고 있어요.",
+            Some(Trend::Same) => " 이전과 거리가 같아요.",
+            None => "",
+        };
+
+        format!("{proximity}{trend}")
+    }
+
+    /// Says which type is being played, since the range alone may not.
+    pub fn number_type<T: Numeric>(self, range: &RangeInclusive<T>, tolerance: f64) -> String {
+        let (min, max) = (range.start(), range.end());
+        match self {
+            Lang::En if T::IS_FLOAT => format!(
+                "Secret type: {}, from {min} to {max}. Guesses within {tolerance} count.",
+                T::NAME
+            ),
+            Lang::En => format!("Secret type: {}, from {min} to {max}.", T::NAME),
+            Lang::Ko if T::IS_FLOAT => format!(
+                "비밀 숫자는 {min}부터 {max} 사이의 {}입니다. 차이가 {tolerance} 이내면 정답입니다.",
+                T::NAME
+            ),
+            Lang::Ko => format!("비밀 숫자는 {min}부터 {max} 사이의 {}입니다.", T::NAME),
+        }
+    }
+
+    pub fn time_attack(self, total: Duration, per_guess: Duration) -> String {
+        let (total, per_guess) = (total.as_secs(), per_guess.as_secs());
+        match self {
+            Lang::En => format!(
+                "Time attack: win within {total}s, taking at most {per_guess}s per guess."
+            ),
+            Lang::Ko => format!(
+                "타임 어택: {total}초 안에 맞혀야 하고, 한 번 추측할 때마다 {per_guess}초가 주어집니다."
+            ),
+        }
+    }
+
+    /// Rounded up, so the clock never says 0s while there is time left.
+    pub fn time_left(self, left: Duration) -> String {
+        let seconds = left.as_millis().div_ceil(1000);
+        match self {
+            Lang::En => format!("{seconds}s left"),
+            Lang::Ko => format!("남은 시간 {seconds}초"),
+        }
+    }
+
+    pub fn too_slow(self) -> &'static str {
+        match self {
+            Lang::En => "Too slow! That counts as a miss.",
+            Lang::Ko => "너무 느립니다! 한 번 틀린 것으로 칩니다.",
+        }
+    }
+
+    pub fn too_late(self) -> &'static str {
+        match self {
+            Lang::En => "That guess was for the last prompt, so it does not count.",
+            Lang::Ko => "방금 입력은 지난 차례의 추측이라 세지 않습니다.",
+        }
+    }
+
+    pub fn score(self, points: u32) -> String {
+        match self {
+            Lang::En => format!("Score: {points}"),
+            Lang::Ko => format!("점수: {points}"),
+        }
+    }
+
+    pub fn word_title(self) -> &'static str {
+        match self {
+            Lang::En => "Guess the five-letter word!",
+            Lang::Ko => "다섯 글자 영어 단어를 맞혀 보세요!",
+        }
+    }
+
+    pub fn word_prompt(self) -> &'static str {
+        match self {
+            Lang::En => "Please input your word.",
+            Lang::Ko => "추측한 단어를 입력하세요.",
+        }
+    }
+
+    pub fn word_lose(self, answer: &str) -> String {
+        let answer = answer.to_uppercase();
+        match self {
+            Lang::En => format!("You lose, the word was {answer}."),
+            Lang::Ko => format!("졌습니다. 정답은 {answer}입니다."),
+        }
+    }
+
+    pub fn word_error(self, error: &WordError) -> String {
+        match self {
+            Lang::En => error.to_string(),
+            Lang::Ko => match error {
+                WordError::WrongLength(len) => {
+                    format!("다섯 글자를 입력하세요. 입력한 글자 수: {len}")
+                }
+                WordError::NotALetter(c) => format!("{c:?}은(는) 알파벳이 아닙니다."),
+                WordError::NotInList(word) => format!("{word:?}은(는) 단어 목록에 없습니다."),
+                WordError::MustPlace { letter, position } => format!(
+                    "하드 모드: {}번째 글자는 {}이어야 합니다.",
+                    position + 1,
+                    letter.to_ascii_uppercase()
+                ),
+                WordError::MustUse(letter) => format!(
+                    "하드 모드: {}을(를) 반드시 사용해야 합니다.",
+                    letter.to_ascii_uppercase()
+                ),
+            },
+        }
+    }
+
+    pub fn reverse_intro(self, range: &RangeInclusive<u32>) -> String {
+        let (min, max) = (range.start(), range.end());
+        match self {
+            Lang::En => format!("Think of a number from {min} to {max}, and I will guess it."),
+            Lang::Ko => {
+                format!("{min}부터 {max} 사이의 숫자를 하나 생각하세요. 제가 맞혀 보겠습니다.")
+            }
+        }
+    }
+
+    pub fn reverse_help(self) -> &'static str {
+        match self {
+            Lang::En => "Answer each guess with higher, lower or correct (h/l/c).",
+            Lang::Ko => {
+                "생각한 숫자가 더 크면 higher, 더 작으면 lower, 맞으면 correct로 답하세요 (h/l/c)."
+            }
+        }
+    }
+
+    pub fn reverse_guess(self, guess: u32) -> String {
+        match self {
+            Lang::En => format!("Is it {guess}?"),
+            Lang::Ko => format!("{guess}인가요?"),
+        }
+    }
+
+    pub fn reverse_bad_answer(self) -> &'static str {
+        match self {
+            Lang::En => "Please answer higher, lower or correct.",
+            Lang::Ko => "higher, lower, correct 중 하나로 답하세요.",
+        }
+    }
+
+    pub fn cheating(self, cheating: &Cheating) -> String {
+        if self == Lang::En {
+            return format!("Cheater! {cheating}");
+        }
+
+        let guess = cheating.guess;
+        let direction = match cheating.answer {
+            Answer::Higher => format!("{guess}보다 클"),
+            Answer::Lower => format!("{guess}보다 작을"),
+            Answer::Correct => format!("{guess}일"),
+        };
+        let left = if cheating.low == cheating.high {
+            cheating.low.to_string()
+        } else {
+            format!("{}부터 {} 사이", cheating.low, cheating.high)
+        };
+        format!(
+            "속임수! 생각한 숫자는 {direction} 수 없습니다. 앞선 답에 맞는 숫자는 {left}뿐입니다."
+        )
+    }
+
+    pub fn reverse_found(self, found: u32, guesses: u32, worst_case: u32) -> String {
+        match self {
+            Lang::En => format!(
+                "Your number is {found}! I needed {}; binary search never needs more than {} here.",
+                self.guesses(guesses),
+                self.guesses(worst_case)
+            ),
+            Lang::Ko => format!(
+                "생각한 숫자는 {found}입니다! {} 만에 찾았습니다. 이 범위에서 이진 탐색은 {}을 넘지 않습니다.",
+                self.guesses(guesses),
+                self.guesses(worst_case)
+            ),
+        }
+    }
+
+    pub fn bench_intro(self, games: usize, range: &RangeInclusive<u32>, seed: u64) -> String {
+        let (min, max) = (range.start(), range.end());
+        match self {
+            Lang::En => format!(
+                "{} per strategy, range {min} to {max}, seed {seed}",
+                self.games(games)
+            ),
+            Lang::Ko => format!(
+                "전략마다 {}, 범위 {min}부터 {max}, 시드 {seed}",
+                self.games(games)
+            ),
+        }
+    }
+
+    /// Column headings for the bench table, padded to line up with its rows.
+    pub fn bench_columns(self) -> &'static str {
+        match self {
+            Lang::En => "Strategy      Mean  Median  Worst",
+            Lang::Ko => "전략          평균  중앙값   최악",
+        }
+    }
+
+    pub fn no_games(self) -> &'static str {
+        match self {
+            Lang::En => "No games played yet.",
+            Lang::Ko => "아직 플레이한 게임이 없습니다.",
+        }
+    }
+
+    pub fn games_played(self, games: u32) -> String {
+        match self {
+            Lang::En => format!("Games played:   {games}"),
+            Lang::Ko => format!("플레이한 게임: {games}"),
+        }
+    }
+
+    pub fn games_won(self, wins: u32) -> String {
+        match self {
+            Lang::En => format!("Games won:      {wins}"),
+            Lang::Ko => format!("이긴 게임:     {wins}"),
+        }
+    }
+
+    pub fn best_score(self, attempts: u32) -> String {
+        match self {
+            Lang::En => format!("Best score:     {}", self.attempts(attempts)),
+            Lang::Ko => format!("최고 기록:     {}", self.attempts(attempts)),
+        }
+    }
+
+    pub fn average_attempts(self, average: f64) -> String {
+        match self {
+            Lang::En => format!("Average win:    {average:.2} attempts"),
+            Lang::Ko => format!("승리 평균:     {average:.2}번"),
+        }
+    }
+
+    pub fn current_streak(self, streak: u32) -> String {
+        match self {
+            Lang::En => format!("Current streak: {streak}"),
+            Lang::Ko => format!("현재 연승:     {streak}"),
+        }
+    }
+
+    pub fn longest_streak(self, streak: u32) -> String {
+        match self {
+            Lang::En => format!("Longest streak: {streak}"),
+            Lang::Ko => format!("최장 연승:     {streak}"),
+        }
+    }
+
+    pub fn attempts_per_game(self) -> &'static str {
+        match self {
+            Lang::En => "Attempts per game:",
+            Lang::Ko => "게임별 시도 횟수:",
+        }
+    }
+
+    pub fn no_wins(self) -> &'static str {
+        match self {
+            Lang::En => "No wins recorded yet.",
+            Lang::Ko => "아직 기록된 승리가 없습니다.",
+        }
+    }
+
+    /// The heading over one range's leaderboard.
+    pub fn leaderboard_range(self, min: u32, max: u32) -> String {
+        match self {
+            Lang::En => format!("Range {min} to {max}:"),
+            Lang::Ko => format!("{min}부터 {max}까지:"),
+        }
+    }
+
+    pub fn leaderboard_words(self) -> &'static str {
+        match self {
+            Lang::En => "Word games:",
+            Lang::Ko => "단어 게임:",
+        }
+    }
+
+    /// Column headings for the leaderboard, with a score column for
+    /// time-attack games.
+    pub fn leaderboard_columns(self, scored: bool) -> &'static str {
+        match (self, scored) {
+            (Lang::En, true) => "Rank  Player           Score  Attempts  Time     Seed",
+            (Lang::En, false) => "Rank  Player           Attempts  Time     Seed",
+            (Lang::Ko, true) => "순위  이름              점수      시도  시간     시드",
+            (Lang::Ko, false) => "순위  이름                  시도  시간     시드",
+        }
+    }
+
+    pub fn scores_unreadable(self, err: impl Display) -> String {
+        match self {
+            Lang::En => format!("Problem reading scores: {err}"),
+            Lang::Ko => format!("점수 파일을 읽지 못했습니다: {err}"),
+        }
+    }
+
+    pub fn cannot_listen(self, addr: &str, err: impl Display) -> String {
+        match self {
+            Lang::En => format!("Could not listen on {addr}: {err}"),
+            Lang::Ko => format!("{addr}에서 연결을 받을 수 없습니다: {err}"),
+        }
+    }
+
+    pub fn listening(self, addr: &str, seed: u64) -> String {
+        match self {
+            Lang::En => format!("Listening on {addr} (seed {seed})"),
+            Lang::Ko => format!("{addr}에서 연결을 기다리는 중 (시드 {seed})"),
+        }
+    }
+
+    pub fn serving_api(self, addr: &str, ttl: Duration) -> String {
+        let ttl = ttl.as_secs();
+        match self {
+            Lang::En => {
+                format!("Serving the JSON API on http://{addr} (idle games expire after {ttl}s)")
+            }
+            Lang::Ko => format!(
+                "http://{addr}에서 JSON API를 제공합니다 ({ttl}초 동안 쓰지 않은 게임은 사라집니다)"
+            ),
+        }
+    }
+
+    pub fn server_stopped(self, err: impl Display) -> String {
+        match self {
+            Lang::En => format!("Server stopped: {err}"),
+            Lang::Ko => format!("서버가 멈췄습니다: {err}"),
+        }
+    }
+
+    pub fn cannot_open(self, path: impl Display, err: impl Display) -> String {
+        match self {
+            Lang::En => format!("Could not open {path}: {err}"),
+            Lang::Ko => format!("{path}을(를) 열 수 없습니다: {err}"),
+        }
+    }
+
+    pub fn replay_matches(self, outcome: &impl Debug) -> String {
+        match self {
+            Lang::En => format!("Replay matches the recording: {outcome:?}"),
+            Lang::Ko => format!("다시 실행한 결과가 기록과 같습니다: {outcome:?}"),
+        }
+    }
+
+    pub fn replay_failed(self, err: impl Display) -> String {
+        match self {
+            Lang::En => format!("Replay failed: {err}"),
+            Lang::Ko => format!("다시 실행하지 못했습니다: {err}"),
+        }
+    }
+
+    pub fn server_welcome(self) -> &'static str {
+        match self {
+            Lang::En => "Welcome to the guessing game! What is your name?",
+            Lang::Ko => "숫자 맞히기 게임에 오신 것을 환영합니다! 이름이 무엇인가요?",
+        }
+    }
+
+    pub fn server_greeting(self, name: &str, round: u32, range: &RangeInclusive<u32>) -> String {
+        let (min, max) = (range.start(), range.end());
+        match self {
+            Lang::En => format!("Hi {name}! Round {round}: guess the number from {min} to {max}."),
+            Lang::Ko => format!(
+                "안녕하세요, {name}님! {round}라운드: {min}부터 {max} 사이의 숫자를 맞혀 보세요."
+            ),
+        }
+    }
+
+    pub fn player_joined(self, name: &str) -> String {
+        match self {
+            Lang::En => format!("{name} joined the game."),
+            Lang::Ko => format!("{name}님이 게임에 들어왔습니다."),
+        }
+    }
+
+    pub fn player_left(self, name: &str) -> String {
+        match self {
+            Lang::En => format!("{name} left the game."),
+            Lang::Ko => format!("{name}님이 게임을 떠났습니다."),
+        }
+    }
+
+    pub fn round_won(self, name: &str, guess: u32, round: u32) -> String {
+        let next = round + 1;
+        match self {
+            Lang::En => format!(
+                "{name} guessed {guess} and wins round {round}! Round {next} starts now."
+            ),
+            Lang::Ko => format!(
+                "{name}님이 {guess}을(를) 맞혀 {round}라운드에서 이겼습니다! 이제 {next}라운드를 시작합니다."
+            ),
+        }
+    }
+
+    /// The server's own log of who is playing.
+    pub fn players_log(self, name: &str, joined: bool, playing: usize) -> String {
+        match (self, joined) {
+            (Lang::En, true) => format!("{name} joined ({playing} playing)"),
+            (Lang::En, false) => format!("{name} left ({playing} playing)"),
+            (Lang::Ko, true) => format!("{name} 입장 ({playing}명 참여 중)"),
+            (Lang::Ko, false) => format!("{name} 퇴장 ({playing}명 참여 중)"),
+        }
+    }
+
+    pub fn round_won_log(self, name: &str, guess: u32, round: u32) -> String {
+        match self {
+            Lang::En => format!("Round {round} won by {name} with {guess}"),
+            Lang::Ko => format!("{round}라운드 승자: {name} ({guess})"),
+        }
+    }
+
+    pub fn player_dropped_log(self, name: &str) -> String {
+        match self {
+            Lang::En => format!("{name} dropped: not reading"),
+            Lang::Ko => format!("{name} 연결 끊음: 메시지를 받지 않음"),
+        }
+    }
+
+    pub fn accept_failed(self, err: impl Display) -> String {
+        match self {
+            Lang::En => format!("Failed to accept a connection: {err}"),
+            Lang::Ko => format!("연결을 받지 못했습니다: {err}"),
+        }
+    }
+
+    pub fn request_failed(self, err: impl Display) -> String {
+        match self {
+            Lang::En => format!("Request failed: {err}"),
+            Lang::Ko => format!("요청을 처리하지 못했습니다: {err}"),
+        }
+    }
+
+    pub fn player_disconnected(self, id: usize, err: impl Display) -> String {
+        match self {
+            Lang::En => format!("Player {id} disconnected: {err}"),
+            Lang::Ko => format!("{id}번 플레이어의 연결이 끊겼습니다: {err}"),
+        }
+    }
+}
+
+/// `count` followed by whichever English noun agrees with it.
+fn plural(count: u64, one: &str, many: &str) -> String {
+    let noun = if count == 1 { one } else { many };
+    format!("{count} {noun}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cheating_names_what_is_left() {
+        let cheating = Cheating {
+            guess: 5,
+            answer: Answer::Higher,
+            low: 3,
+            high: 5,
+        };
+
+        assert_eq!(
+            Lang::En.cheating(&cheating),
+            "Cheater! Your number cannot be higher than 5: your earlier answers only leave 3 to 5."
+        );
+        assert_eq!(
+            Lang::Ko.cheating(&cheating),
+            "속임수! 생각한 숫자는 5보다 클 수 없습니다. 앞선 답에 맞는 숫자는 3부터 5 사이뿐입니다."
+        );
+    }
+
+    #[test]
+    fn counts_agree_with_their_nouns() {
+        assert_eq!(Lang::En.attempts(1), "1 attempt");
+        assert_eq!(Lang::En.attempts(0), "0 attempts");
+        assert_eq!(Lang::En.guesses(1), "1 guess");
+        assert_eq!(Lang::En.guesses(2), "2 guesses");
+        assert_eq!(Lang::En.games(1), "1 game");
+        assert_eq!(Lang::Ko.guesses(1), "1번");
+
+        assert_eq!(
+            Lang::En.reverse_found(1, 1, 1),
+            "Your number is 1! I needed 1 guess; binary search never needs more than 1 guess here."
+        );
+        assert_eq!(
+            Lang::En.reverse_found(42, 3, 7),
+            "Your number is 42! I needed 3 guesses; binary search never needs more than 7 guesses here."
+        );
+        assert_eq!(
+            Lang::Ko.reverse_found(42, 3, 7),
+            "생각한 숫자는 42입니다! 3번 만에 찾았습니다. 이 범위에서 이진 탐색은 7번을 넘지 않습니다."
+        );
+        assert_eq!(Lang::En.win(1), "You win in 1 attempt!");
+        assert!(Lang::En
+            .bench_intro(1, &(1..=100), 0)
+            .starts_with("1 game per strategy"));
+    }
+
+    #[test]
+    fn parses_language_names() {
+        assert_eq!(Lang::parse("KR"), Some(Lang::Ko));
+        assert_eq!(Lang::parse("english"), Some(Lang::En));
+        assert_eq!(Lang::parse("fr"), None);
+    }
+}
@@ -0,0 +1,608 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+pub mod api;
+pub mod config;
+pub mod guess;
+pub mod hint;
+pub mod i18n;
+pub mod numeric;
+pub mod record;
+pub mod reverse;
+pub mod script;
+pub mod secret;
+pub mod server;
+pub mod solver;
+pub mod stats;
+pub mod timed;
+pub mod tui;
+pub mod word;
+
+pub use crate::config::{Command, Config, GameMode, NumberType, SeedMode};
+pub use crate::guess::GuessError;
+pub use crate::hint::Hint;
+pub use crate::i18n::Lang;
+pub use crate::numeric::Numeric;
+pub use crate::secret::{Number, Secret};
+
+/// Where guesses come from, one raw line at a time.
+///
+/// Returning `None` means the input is exhausted and the game stops.
+pub trait GuessSource {
+    fn next_guess(&mut self) -> Option<String>;
+}
+
+impl<G: GuessSource + ?Sized> GuessSource for Box<G> {
+    fn next_guess(&mut self) -> Option<String> {
+        (**self).next_guess()
+    }
+}
+
+/// Everything the game wants to tell the player.
+///
+/// The type parameter is the kind of secret being played; it defaults to
+/// the number game.
+pub trait Reporter<S: Secret = Number> {
+    fn start(&mut self);
+    fn prompt(&mut self);
+
+    /// Sees every raw line before it is parsed; ignored by default.
+    fn input(&mut self, _line: &str) {}
+
+    fn invalid(&mut self, error: &S::Error);
+    fn guessed(&mut self, guess: S::Guess);
+    fn feedback(&mut self, feedback: S::Feedback);
+
+    /// The attempts used so far, after every turn; ignored by default.
+    ///
+    /// Rejected input counts too in strict mode, so this can be more than
+    /// the number of guesses reported.
+    fn attempts(&mut self, _used: u32) {}
+
+    /// Only called when hints are turned on; ignored by default.
+    fn hint(&mut self, _hint: &Hint) {}
+
+    /// Time-attack games report the clock before each prompt; ignored by default.
+    fn time_left(&mut self, _left: Duration) {}
+
+    /// A time-attack guess took too long and counted as a miss.
+    fn timed_out(&mut self) {}
+
+    /// A line finished just after its guess timed out, and dropped rather
+    /// than scored against the next prompt; ignored by default.
+    fn late(&mut self, _line: &str) {}
+
+    fn finish(&mut self, outcome: &Outcome<S::Answer>);
+}
+
+impl<S: Secret, P: Reporter<S> + ?Sized> Reporter<S> for Box<P> {
+    fn start(&mut self) {
+        (**self).start();
+    }
+
+    fn prompt(&mut self) {
+        (**self).prompt();
+    }
+
+    fn input(&mut self, line: &str) {
+        (**self).input(line);
+    }
+
+    fn invalid(&mut self, error: &S::Error) {
+        (**self).invalid(error);
+    }
+
+    fn guessed(&mut self, guess: S::Guess) {
+        (**self).guessed(guess);
+    }
+
+    fn feedback(&mut self, feedback: S::Feedback) {
+        (**self).feedback(feedback);
+    }
+
+    fn attempts(&mut self, used: u32) {
+        (**self).attempts(used);
+    }
+
+    fn hint(&mut self, hint: &Hint) {
+        (**self).hint(hint);
+    }
+
+    fn time_left(&mut self, left: Duration) {
+        (**self).time_left(left);
+    }
+
+    fn timed_out(&mut self) {
+        (**self).timed_out();
+    }
+
+    fn late(&mut self, line: &str) {
+        (**self).late(line);
+    }
+
+    fn finish(&mut self, outcome: &Outcome<S::Answer>) {
+        (**self).finish(outcome);
+    }
+}
+
+/// How a game ended; `T` is the secret revealed on a loss.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "result", rename_all = "snake_case")]
+pub enum Outcome<T = u32> {
+    Won { attempts: u32 },
+    Lost { attempts: u32, secret: T },
+    Quit { attempts: u32 },
+}
+
+impl<T> Outcome<T> {
+    pub fn attempts(&self) -> u32 {
+        match self {
+            Outcome::Won { attempts }
+            | Outcome::Lost { attempts, .. }
+            | Outcome::Quit { attempts } => *attempts,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Game<S: Secret = Number> {
+    secret: S,
+    attempts: u32,
+    max_attempts: Option<u32>,
+    strict: bool,
+    outcome: Option<Outcome<S::Answer>>,
+}
+
+impl<S: Secret> Game<S> {
+    pub fn with_secret(secret: S) -> Game<S> {
+        Game {
+            secret,
+            attempts: 0,
+            max_attempts: None,
+            strict: false,
+            outcome: None,
+        }
+    }
+
+    /// Ends the game as lost once `max` guesses have missed.
+    pub fn with_max_attempts(mut self, max: u32) -> Game<S> {
+        self.max_attempts = Some(max);
+        self
+    }
+
+    /// In strict mode every rejected input costs an attempt.
+    pub fn with_strict(mut self, strict: bool) -> Game<S> {
+        self.strict = strict;
+        self
+    }
+
+    pub fn answer(&self) -> S::Answer {
+        self.secret.answer()
+    }
+
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    pub fn max_attempts(&self) -> Option<u32> {
+        self.max_attempts
+    }
+
+    pub fn remaining_attempts(&self) -> Option<u32> {
+        self.max_attempts
+            .map(|max| max.saturating_sub(self.attempts))
+    }
+
+    pub fn outcome(&self) -> Option<Outcome<S::Answer>> {
+        self.outcome.clone()
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.outcome.is_some()
+    }
+
+    pub fn parse(&self, input: &str) -> Result<S::Guess, S::Error> {
+        self.secret.parse(input)
+    }
+
+    /// The hint for the latest missed guess, if the secret gives hints.
+    pub fn hint(&self) -> Option<Hint> {
+        self.secret.hint()
+    }
+
+    /// Counts the guess and scores it against the secret.
+    ///
+    /// A guess the secret refuses is treated like any other rejected input.
+    pub fn submit(&mut self, guess: &S::Guess) -> Result<S::Feedback, S::Error> {
+        let feedback = match self.secret.check(guess) {
+            Ok(feedback) => feedback,
+            Err(err) => {
+                self.reject();
+                return Err(err);
+            }
+        };
+
+        self.attempts += 1;
+        if self.secret.is_solved(&feedback) {
+            self.outcome = Some(Outcome::Won {
+                attempts: self.attempts,
+            });
+        } else {
+            self.check_attempts();
+        }
+
+        Ok(feedback)
+    }
+
+    /// Records input that was not a valid guess.
+    ///
+    /// It only costs an attempt in strict mode.
+    pub fn reject(&mut self) {
+        if self.strict {
+            self.attempts += 1;
+            self.check_attempts();
+        }
+    }
+
+    /// Counts an attempt that produced no guess at all, such as one that
+    /// ran out of time. Unlike `reject`, it always costs an attempt.
+    pub fn miss(&mut self) {
+        self.attempts += 1;
+        self.check_attempts();
+    }
+
+    fn check_attempts(&mut self) {
+        if self.remaining_attempts() == Some(0) {
+            self.outcome = Some(Outcome::Lost {
+                attempts: self.attempts,
+                secret: self.secret.answer(),
+            });
+        }
+    }
+
+    /// Ends the game as lost straight away, as when the clock runs out.
+    pub fn lose(&mut self) -> Outcome<S::Answer> {
+        let outcome = Outcome::Lost {
+            attempts: self.attempts,
+            secret: self.secret.answer(),
+        };
+        self.outcome = Some(outcome.clone());
+        outcome
+    }
+
+    pub fn quit(&mut self) -> Outcome<S::Answer> {
+        let outcome = Outcome::Quit {
+            attempts: self.attempts,
+        };
+        self.outcome = Some(outcome.clone());
+        outcome
+    }
+}
+
+impl Game {
+    pub fn new(secret: u32) -> Game {
+        Game::with_secret(Number::new(secret, 0..=u32::MAX))
+    }
+
+    pub fn from_config<R: Rng>(rng: &mut R, config: &Config) -> Game {
+        let game = Game::with_rng(rng, config.range.clone())
+            .with_strict(config.strict)
+            .with_hints(config.hints);
+
+        match config.max_attempts {
+            Some(max) => game.with_max_attempts(max),
+            None => game,
+        }
+    }
+}
+
+impl<T: Numeric> Game<Number<T>> {
+    pub fn with_rng<R: Rng>(rng: &mut R, range: RangeInclusive<T>) -> Game<Number<T>> {
+        Game::with_secret(Number::new(rng.gen_range(range.clone()), range))
+    }
+
+    /// Guesses outside `range` are rejected instead of compared.
+    pub fn with_range(mut self, range: RangeInclusive<T>) -> Game<Number<T>> {
+        self.secret.set_range(range);
+        self
+    }
+
+    /// Adds warmer/colder hints to every missed guess.
+    pub fn with_hints(mut self, hints: bool) -> Game<Number<T>> {
+        self.secret.set_hints(hints);
+        self
+    }
+
+    /// How close a float guess must be to count; integers ignore it.
+    pub fn with_tolerance(mut self, tolerance: f64) -> Game<Number<T>> {
+        self.secret.set_tolerance(tolerance);
+        self
+    }
+
+    pub fn secret(&self) -> T {
+        self.secret.value()
+    }
+
+    pub fn range(&self) -> &RangeInclusive<T> {
+        self.secret.range()
+    }
+
+    /// The hint for the latest missed guess, if hints are on.
+    pub fn last_hint(&self) -> Option<Hint> {
+        self.secret.hint()
+    }
+
+    /// Counts the guess and compares it with the secret.
+    pub fn guess(&mut self, guess: T) -> Ordering {
+        self.submit(&guess).expect("numbers can always be compared")
+    }
+}
+
+/// Drives `game` until it is won, lost or `source` runs dry.
+pub fn play<S, G, P>(game: &mut Game<S>, source: &mut G, reporter: &mut P) -> Outcome<S::Answer>
+where
+    S: Secret,
+    G: GuessSource,
+    P: Reporter<S>,
+{
+    reporter.start();
+
+    loop {
+        reporter.prompt();
+
+        let guess = match source.next_guess() {
+            Some(line) => line,
+            None => {
+                let outcome = game.quit();
+                reporter.finish(&outcome);
+                return outcome;
+            }
+        };
+
+        take_turn(game, reporter, &guess);
+
+        if let Some(outcome) = game.outcome() {
+            reporter.finish(&outcome);
+            return outcome;
+        }
+    }
+}
+
+/// Handles one line of input: parse it, score it and report the result.
+pub(crate) fn take_turn<S: Secret, P: Reporter<S>>(
+    game: &mut Game<S>,
+    reporter: &mut P,
+    line: &str,
+) {
+    reporter.input(line);
+
+    match game.parse(line) {
+        Ok(guess) => {
+            reporter.guessed(guess.clone());
+
+            match game.submit(&guess) {
+                Ok(feedback) => {
+                    reporter.feedback(feedback);
+
+                    if let Some(hint) = game.hint() {
+                        reporter.hint(&hint);
+                    }
+                }
+                Err(err) => reporter.invalid(&err),
+            }
+        }
+        Err(err) => {
+            game.reject();
+            reporter.invalid(&err);
+        }
+    }
+
+    reporter.attempts(game.attempts());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    /// Hands out a fixed list of lines, then runs dry.
+    struct Script(std::vec::IntoIter<String>);
+
+    impl Script {
+        fn new<L: ToString>(lines: &[L]) -> Script {
+            let lines: Vec<String> = lines.iter().map(L::to_string).collect();
+            Script(lines.into_iter())
+        }
+    }
+
+    impl GuessSource for Script {
+        fn next_guess(&mut self) -> Option<String> {
+            self.0.next()
+        }
+    }
+
+    /// Writes down every call as a short string.
+    #[derive(Default)]
+    struct Log(Vec<String>);
+
+    impl Reporter for Log {
+        fn start(&mut self) {
+            self.0.push("start".into());
+        }
+
+        fn prompt(&mut self) {
+            self.0.push("prompt".into());
+        }
+
+        fn invalid(&mut self, error: &GuessError) {
+            self.0.push(format!("invalid {error}"));
+        }
+
+        fn guessed(&mut self, guess: u32) {
+            self.0.push(format!("guessed {guess}"));
+        }
+
+        fn feedback(&mut self, ordering: Ordering) {
+            self.0.push(format!("{ordering:?}"));
+        }
+
+        fn hint(&mut self, hint: &Hint) {
+            self.0.push(format!("hint {hint}"));
+        }
+
+        fn finish(&mut self, outcome: &Outcome) {
+            self.0.push(format!("{outcome:?}"));
+        }
+    }
+
+    /// A game on 1 to 100 whose secret comes from `seed`.
+    fn seeded(seed: u64) -> Game {
+        Game::with_rng(&mut StdRng::seed_from_u64(seed), 1..=100)
+    }
+
+    #[test]
+    fn seeded_games_are_repeatable() {
+        assert_eq!(seeded(42).secret(), seeded(42).secret());
+        assert!((1..=100).contains(&seeded(42).secret()));
+    }
+
+    #[test]
+    fn guess_compares_with_the_secret() {
+        let mut game = Game::new(50);
+
+        assert_eq!(game.guess(49), Ordering::Less);
+        assert_eq!(game.guess(51), Ordering::Greater);
+        assert_eq!(game.attempts(), 2);
+        assert!(!game.is_over());
+
+        assert_eq!(game.guess(50), Ordering::Equal);
+        assert_eq!(game.attempts(), 3);
+        assert_eq!(game.outcome(), Some(Outcome::Won { attempts: 3 }));
+    }
+
+    #[test]
+    fn guess_handles_the_ends_of_the_range() {
+        let mut game = Game::new(0);
+        assert_eq!(game.guess(u32::MAX), Ordering::Greater);
+        assert_eq!(game.guess(0), Ordering::Equal);
+
+        let mut game = Game::new(u32::MAX);
+        assert_eq!(game.guess(0), Ordering::Less);
+        assert_eq!(game.guess(u32::MAX), Ordering::Equal);
+    }
+
+    #[test]
+    fn play_reports_every_step_of_a_win() {
+        let mut game = seeded(1);
+        let secret = game.secret();
+        let (low, high) = (secret - 1, secret + 1);
+        let lines = [
+            low.to_string(),
+            "abc".into(),
+            high.to_string(),
+            secret.to_string(),
+            "never read".into(),
+        ];
+        let mut log = Log::default();
+
+        let outcome = play(&mut game, &mut Script::new(&lines), &mut log);
+
+        assert_eq!(outcome, Outcome::Won { attempts: 3 });
+        assert_eq!(
+            log.0,
+            [
+                "start".to_string(),
+                "prompt".into(),
+                format!("guessed {low}"),
+                "Less".into(),
+                "prompt".into(),
+                "invalid \"abc\" is not a number.".into(),
+                "prompt".into(),
+                format!("guessed {high}"),
+                "Greater".into(),
+                "prompt".into(),
+                format!("guessed {secret}"),
+                "Equal".into(),
+                "Won { attempts: 3 }".into(),
+            ]
+        );
+    }
+
+    #[test]
+    fn play_loses_once_the_attempts_run_out() {
+        let mut game = Game::new(7).with_max_attempts(2);
+        let mut log = Log::default();
+
+        let outcome = play(&mut game, &mut Script::new(&["1", "2", "7"]), &mut log);
+
+        assert_eq!(
+            outcome,
+            Outcome::Lost {
+                attempts: 2,
+                secret: 7
+            }
+        );
+        assert_eq!(log.0.last().unwrap(), "Lost { attempts: 2, secret: 7 }");
+        assert!(!log.0.contains(&"guessed 7".to_string()));
+    }
+
+    #[test]
+    fn play_quits_when_the_input_runs_dry() {
+        let mut game = Game::new(7);
+        let mut log = Log::default();
+
+        let outcome = play(&mut game, &mut Script::new(&["1"]), &mut log);
+
+        assert_eq!(outcome, Outcome::Quit { attempts: 1 });
+        assert_eq!(log.0.last().unwrap(), "Quit { attempts: 1 }");
+        assert_eq!(game.outcome(), Some(outcome));
+    }
+
+    #[test]
+    fn strict_games_count_rejected_input() {
+        let lines = ["abc", "0", "7"];
+
+        let mut relaxed = Game::new(7).with_range(1..=10);
+        let outcome = play(&mut relaxed, &mut Script::new(&lines), &mut Log::default());
+        assert_eq!(outcome, Outcome::Won { attempts: 1 });
+
+        let mut strict = Game::new(7).with_range(1..=10).with_strict(true);
+        let outcome = play(&mut strict, &mut Script::new(&lines), &mut Log::default());
+        assert_eq!(outcome, Outcome::Won { attempts: 3 });
+
+        let mut limited = Game::new(7)
+            .with_range(1..=10)
+            .with_strict(true)
+            .with_max_attempts(2);
+        let outcome = play(&mut limited, &mut Script::new(&lines), &mut Log::default());
+        assert_eq!(
+            outcome,
+            Outcome::Lost {
+                attempts: 2,
+                secret: 7
+            }
+        );
+    }
+
+    #[test]
+    fn play_passes_hints_on() {
+        let mut game = Game::new(50).with_range(1..=100).with_hints(true);
+        let mut log = Log::default();
+
+        play(&mut game, &mut Script::new(&["1", "49", "50"]), &mut log);
+
+        assert_eq!(
+            log.0.iter().filter(|line| line.starts_with("hint")).count(),
+            2
+        );
+        assert!(log.0.contains(&"hint Burning! Getting warmer.".to_string()));
+    }
+}
@@ -1,35 +1,553 @@
-use rand::Rng;
+use guessing_game::api::Api;
+use guessing_game::record::{self, Entry, Recorder};
+use guessing_game::reverse::{self, Answer, Guesser};
+use guessing_game::script::{self, JsonReporter, Lines, EXIT_USAGE};
+use guessing_game::server::Server;
+use guessing_game::solver;
+use guessing_game::stats::{self, Record, Stats};
+use guessing_game::timed::{self, ChannelSource, TimeLimits};
+use guessing_game::tui::Tui;
+use guessing_game::word::{Letter, Score, Word, WordError};
+use guessing_game::{
+    Command, Config, Game, GameMode, GuessError, GuessSource, Hint, Lang, Number, NumberType,
+    Numeric, Outcome, Reporter,
+};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use std::cmp::Ordering;
-use std::io;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::net::TcpListener;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use std::{env, io, process};
+
+const LEADERBOARD_SIZE: usize = 10;
+
+/// The usual Wordle allowance, used unless `--attempts` says otherwise.
+const WORD_ATTEMPTS: u32 = 6;
+
+struct Stdout {
+    lang: Lang,
+}
+
+impl<T: Numeric> Reporter<Number<T>> for Stdout {
+    fn start(&mut self) {
+        println!("{}", self.lang.title());
+    }
+
+    fn prompt(&mut self) {
+        println!("{}", self.lang.prompt());
+    }
+
+    fn invalid(&mut self, error: &GuessError<T>) {
+        println!("{}", self.lang.guess_error(error));
+    }
+
+    fn guessed(&mut self, guess: T) {
+        println!("{}", self.lang.guessed(guess));
+    }
+
+    fn feedback(&mut self, ordering: Ordering) {
+        match ordering {
+            Ordering::Less => println!("{}", self.lang.too_small()),
+            Ordering::Greater => println!("{}", self.lang.too_big()),
+            Ordering::Equal => {}
+        }
+    }
+
+    fn hint(&mut self, hint: &Hint) {
+        println!("{}", self.lang.hint(hint));
+    }
+
+    fn time_left(&mut self, left: Duration) {
+        println!("{}", self.lang.time_left(left));
+    }
+
+    fn timed_out(&mut self) {
+        println!("{}", self.lang.too_slow());
+    }
+
+    fn late(&mut self, _line: &str) {
+        println!("{}", self.lang.too_late());
+    }
+
+    fn finish(&mut self, outcome: &Outcome<T>) {
+        match outcome {
+            Outcome::Won { attempts } => println!("{}", self.lang.win(*attempts)),
+            Outcome::Lost { secret, .. } => println!("{}", self.lang.lose(secret)),
+            Outcome::Quit { .. } => {}
+        }
+    }
+}
+
+impl Reporter<Word> for Stdout {
+    fn start(&mut self) {
+        println!("{}", self.lang.word_title());
+    }
+
+    fn prompt(&mut self) {
+        println!("{}", self.lang.word_prompt());
+    }
+
+    fn invalid(&mut self, error: &WordError) {
+        println!("{}", self.lang.word_error(error));
+    }
+
+    fn guessed(&mut self, guess: String) {
+        let letters: Vec<String> = guess
+            .chars()
+            .map(|c| format!("{} ", c.to_ascii_uppercase()))
+            .collect();
+        println!("{}", letters.concat().trim_end());
+    }
+
+    fn feedback(&mut self, score: Score) {
+        let squares: String = score
+            .iter()
+            .map(|letter| match letter {
+                Letter::Correct => '🟩',
+                Letter::Present => '🟨',
+                Letter::Absent => '⬜',
+            })
+            .collect();
+        println!("{squares}");
+    }
+
+    fn finish(&mut self, outcome: &Outcome<String>) {
+        match outcome {
+            Outcome::Won { attempts } => println!("{}", self.lang.win(*attempts)),
+            Outcome::Lost { secret, .. } => println!("{}", self.lang.word_lose(secret)),
+            Outcome::Quit { .. } => {}
+        }
+    }
+}
 
 fn main() {
-    println!("Guess the number!");
+    let command = Command::build(env::args()).unwrap_or_else(|err| {
+        eprintln!("Problem parsing arguments: {err}");
+        eprintln!("Usage: guessing_game [play|reverse|bench|serve|api|stats|leaderboard | replay LOG] [--mode number|word|time-attack] [--time-limit SECS] [--guess-time SECS] [--hard] [--type u8..u128|i8..i128|f32|f64] [--tolerance X] [--preset easy|normal|hard] [--min N] [--max N] [--attempts N] [--seed N | --daily] [--strict] [--hints] [--tui] [--lang en|ko] [--name NAME] [--scores PATH] [--games N] [--addr HOST:PORT] [--session-ttl SECS] [--record LOG] [--input PATH] [--json]");
+        eprintln!("Exit status: 0 won, 1 lost, 2 bad arguments, 3 input ended mid-game.");
+        process::exit(EXIT_USAGE);
+    });
+
+    match command {
+        Command::Play(config) => play(&config),
+        Command::Stats(config) => show_stats(&config),
+        Command::Leaderboard(config) => show_leaderboard(&config),
+        Command::Reverse(config) => play_reverse(&config),
+        Command::Bench(config) => bench(&config),
+        Command::Serve(config) => serve(&config),
+        Command::Api(config) => api(&config),
+        Command::Replay(path) => replay(&path),
+    }
+}
+
+fn play(config: &Config) {
+    let seed = config.seed.resolve();
+    let started = Instant::now();
+
+    let outcome = match (config.mode, config.number_type) {
+        (GameMode::Word, _) => play_word(config, seed),
+        (GameMode::TimeAttack, _) => play_time_attack(config, seed),
+        (GameMode::Number, NumberType::U32) => play_number(config, seed),
+        (GameMode::Number, NumberType::U8) => play_typed::<u8>(config, seed),
+        (GameMode::Number, NumberType::U16) => play_typed::<u16>(config, seed),
+        (GameMode::Number, NumberType::U64) => play_typed::<u64>(config, seed),
+        (GameMode::Number, NumberType::U128) => play_typed::<u128>(config, seed),
+        (GameMode::Number, NumberType::I8) => play_typed::<i8>(config, seed),
+        (GameMode::Number, NumberType::I16) => play_typed::<i16>(config, seed),
+        (GameMode::Number, NumberType::I32) => play_typed::<i32>(config, seed),
+        (GameMode::Number, NumberType::I64) => play_typed::<i64>(config, seed),
+        (GameMode::Number, NumberType::I128) => play_typed::<i128>(config, seed),
+        (GameMode::Number, NumberType::F32) => play_typed::<f32>(config, seed),
+        (GameMode::Number, NumberType::F64) => play_typed::<f64>(config, seed),
+    };
+
+    if !config.json {
+        println!("{}", config.lang().seed(seed));
+    }
+
+    let code = script::exit_code(&outcome);
+    let won = match outcome {
+        Outcome::Won { .. } => true,
+        Outcome::Lost { .. } => false,
+        Outcome::Quit { .. } => process::exit(code),
+    };
+    let attempts = outcome.attempts();
+
+    // The scores file only has room for `u32` ranges.
+    if config.number_type != NumberType::U32 {
+        if !won {
+            process::exit(code);
+        }
+        return;
+    }
+
+    let (min, max) = match config.mode {
+        GameMode::Number | GameMode::TimeAttack => (*config.range.start(), *config.range.end()),
+        GameMode::Word => (0, 0),
+    };
+    let record = Record {
+        player: config.player_name(),
+        mode: config.mode,
+        min,
+        max,
+        attempts,
+        won,
+        duration: started.elapsed(),
+        seed,
+    };
+    if let Some(score) = record.score().filter(|_| !config.json) {
+        println!("{}", config.lang().score(score));
+    }
+    if let Err(err) = stats::append(&config.scores, &record) {
+        eprintln!(
+            "Could not save the game to {}: {err}",
+            config.scores.display()
+        );
+    }
 
-    let secret_number = rand::thread_rng().gen_range(1..=100);
+    if !won {
+        process::exit(code);
+    }
+}
 
-    loop {
-        println!("Please input your guess.");
+/// `--input` if given, otherwise standard input.
+fn guesses(config: &Config) -> Box<dyn GuessSource> {
+    match &config.input {
+        Some(path) => Box::new(Lines::new(BufReader::new(open_input(path)))),
+        None => Box::new(Lines::new(io::stdin().lock())),
+    }
+}
 
-        let mut guess = String::new();
+fn open_input(path: &Path) -> File {
+    File::open(path).unwrap_or_else(|err| {
+        eprintln!("Could not open {}: {err}", path.display());
+        process::exit(EXIT_USAGE);
+    })
+}
 
-        io::stdin()
-            .read_line(&mut guess)
-            .expect("Failed to read line");
+/// JSON for `--json`, otherwise the usual lines of text.
+fn reporter<T: Numeric>(config: &Config, seed: u64) -> Box<dyn Reporter<Number<T>>> {
+    if config.json {
+        Box::new(JsonReporter::stdout(seed))
+    } else {
+        Box::new(Stdout {
+            lang: config.lang(),
+        })
+    }
+}
 
-        let guess: u32 = match guess.trim().parse() {
-            Ok(num) => num,
-            Err(_) => continue,
+/// The outcome without its secret, whose type differs between modes.
+fn summary<T>(outcome: &Outcome<T>) -> Outcome<()> {
+    match *outcome {
+        Outcome::Won { attempts } => Outcome::Won { attempts },
+        Outcome::Lost { attempts, .. } => Outcome::Lost {
+            attempts,
+            secret: (),
+        },
+        Outcome::Quit { attempts } => Outcome::Quit { attempts },
+    }
+}
+
+fn play_number(config: &Config, seed: u64) -> Outcome<()> {
+    let mut game = Game::from_config(&mut StdRng::seed_from_u64(seed), config);
+
+    let lang = config.lang();
+    let mut reporter: Box<dyn Reporter> = if config.tui {
+        Box::new(Tui::new(
+            io::stdout(),
+            lang,
+            config.range.clone(),
+            config.max_attempts,
+        ))
+    } else {
+        reporter(config, seed)
+    };
+
+    let outcome = match &config.record {
+        Some(path) => {
+            let file = File::create(path).unwrap_or_else(|err| {
+                eprintln!("Could not create {}: {err}", path.display());
+                process::exit(EXIT_USAGE);
+            });
+            let session = Entry::session(config, seed);
+            let mut recorder = Recorder::new(reporter, BufWriter::new(file), session);
+
+            let outcome = guessing_game::play(&mut game, &mut guesses(config), &mut recorder);
+            if let Err(err) = recorder.finish_recording() {
+                eprintln!("Could not write the recording to {}: {err}", path.display());
+            }
+            outcome
+        }
+        None => guessing_game::play(&mut game, &mut guesses(config), &mut reporter),
+    };
+
+    summary(&outcome)
+}
+
+/// The plain number game for any other `--type`.
+fn play_typed<T: Numeric>(config: &Config, seed: u64) -> Outcome<()> {
+    let range = config.typed_range::<T>().unwrap_or_else(|err| {
+        eprintln!("Problem parsing arguments: {err}");
+        process::exit(EXIT_USAGE);
+    });
+
+    if !config.json {
+        println!("{}", config.lang().number_type(&range, config.tolerance()));
+    }
+
+    let game = Game::with_rng(&mut StdRng::seed_from_u64(seed), range)
+        .with_strict(config.strict)
+        .with_hints(config.hints)
+        .with_tolerance(config.tolerance());
+    let mut game = match config.max_attempts {
+        Some(max) => game.with_max_attempts(max),
+        None => game,
+    };
+
+    let outcome = guessing_game::play(&mut game, &mut guesses(config), &mut reporter(config, seed));
+
+    summary(&outcome)
+}
+
+fn play_time_attack(config: &Config, seed: u64) -> Outcome<()> {
+    let mut game = Game::from_config(&mut StdRng::seed_from_u64(seed), config);
+    let limits = TimeLimits {
+        total: config.time_limit,
+        per_guess: config.guess_time,
+        late: timed::LATE_LINE,
+    };
+
+    if !config.json {
+        let lang = config.lang();
+        println!("{}", lang.time_attack(limits.total, limits.per_guess));
+    }
+
+    let mut source = match &config.input {
+        Some(path) => ChannelSource::spawn(BufReader::new(open_input(path))),
+        None => ChannelSource::stdin(),
+    };
+    let outcome = timed::play_timed(&mut game, &mut source, &mut reporter(config, seed), &limits);
+
+    summary(&outcome)
+}
+
+fn play_word(config: &Config, seed: u64) -> Outcome<()> {
+    let word = Word::random(&mut StdRng::seed_from_u64(seed), config.hard);
+    let mut game = Game::with_secret(word)
+        .with_max_attempts(config.max_attempts.unwrap_or(WORD_ATTEMPTS))
+        .with_strict(config.strict);
+
+    let mut reporter = Stdout {
+        lang: config.lang(),
+    };
+    let outcome = guessing_game::play(&mut game, &mut guesses(config), &mut reporter);
+
+    summary(&outcome)
+}
+
+fn play_reverse(config: &Config) {
+    let lang = config.lang();
+    let mut guesser = Guesser::new(config.range.clone());
+
+    println!("{}", lang.reverse_intro(&config.range));
+    println!("{}", lang.reverse_help());
+
+    let mut answers = Lines::new(io::stdin().lock());
+    let found = loop {
+        let guess = guesser.next_guess();
+        println!("{}", lang.reverse_guess(guess));
+
+        let answer = match answers.next_guess() {
+            Some(line) => line,
+            None => return,
         };
 
-        println!("You guessed: {guess}");
+        let answer = match Answer::parse(&answer) {
+            Some(answer) => answer,
+            None => {
+                println!("{}", lang.reverse_bad_answer());
+                continue;
+            }
+        };
+
+        if let Err(cheating) = guesser.answer(guess, answer) {
+            println!("{}", lang.cheating(&cheating));
+            process::exit(1);
+        }
+
+        if let Some(found) = guesser.found() {
+            break found;
+        }
+    };
+
+    println!(
+        "{}",
+        lang.reverse_found(
+            found,
+            guesser.guesses(),
+            reverse::worst_case_guesses(&config.range)
+        )
+    );
+}
+
+fn bench(config: &Config) {
+    let lang = config.lang();
+    let seed = config.seed.resolve();
+
+    println!("{}", lang.bench_intro(config.games, &config.range, seed));
+    println!("{}", lang.bench_columns());
+
+    for mut strategy in solver::all_strategies() {
+        let report = solver::simulate(strategy.as_mut(), &config.range, config.games, seed);
+
+        println!(
+            "{:<10} {:>7.2} {:>7.1} {:>6}",
+            report.strategy, report.mean, report.median, report.worst
+        );
+    }
+}
+
+fn listen(config: &Config) -> TcpListener {
+    TcpListener::bind(&config.addr).unwrap_or_else(|err| {
+        eprintln!("{}", config.lang().cannot_listen(&config.addr, err));
+        process::exit(1);
+    })
+}
+
+fn serve(config: &Config) {
+    let lang = config.lang();
+    let listener = listen(config);
+
+    let seed = config.seed.resolve();
+    println!("{}", lang.listening(&config.addr, seed));
+
+    let server = Server::new(config.range.clone(), seed).with_lang(lang);
+    if let Err(err) = server.run(listener) {
+        eprintln!("{}", lang.server_stopped(err));
+        process::exit(1);
+    }
+}
+
+fn api(config: &Config) {
+    let lang = config.lang();
+    let listener = listen(config);
+
+    println!("{}", lang.serving_api(&config.addr, config.session_ttl));
+
+    let api = Api::new(
+        config.range.clone(),
+        config.max_attempts,
+        config.session_ttl,
+    )
+    .with_lang(lang);
+    if let Err(err) = api.run(listener) {
+        eprintln!("{}", lang.server_stopped(err));
+        process::exit(1);
+    }
+}
+
+/// Replays take no options, so the language comes from the environment.
+fn replay(path: &Path) {
+    let lang = Lang::from_env();
+    let file = File::open(path).unwrap_or_else(|err| {
+        eprintln!("{}", lang.cannot_open(path.display(), err));
+        process::exit(EXIT_USAGE);
+    });
+
+    let result =
+        record::read_log(BufReader::new(file)).and_then(|entries| record::replay(&entries));
+    match result {
+        Ok(outcome) => println!("{}", lang.replay_matches(&outcome)),
+        Err(err) => {
+            eprintln!("{}", lang.replay_failed(err));
+            process::exit(1);
+        }
+    }
+}
+
+fn load_records(config: &Config) -> Vec<Record> {
+    let records = stats::load(&config.scores).unwrap_or_else(|err| {
+        eprintln!("{}", config.lang().scores_unreadable(err));
+        process::exit(1);
+    });
 
-        match guess.cmp(&secret_number) {
-            Ordering::Less => println!("Too small!"),
-            Ordering::Greater => println!("Too big!"),
-            Ordering::Equal => {
-                println!("You win!");
-                break;
+    records
+        .into_iter()
+        .filter(|record| record.mode == config.mode)
+        .filter(|record| match &config.player {
+            Some(player) => &record.player == player,
+            None => true,
+        })
+        .collect()
+}
+
+fn show_stats(config: &Config) {
+    let lang = config.lang();
+    let stats = Stats::from_records(&load_records(config));
+
+    if stats.games == 0 {
+        println!("{}", lang.no_games());
+        return;
+    }
+
+    println!("{}", lang.games_played(stats.games));
+    println!("{}", lang.games_won(stats.wins));
+    if let Some(best) = stats.best {
+        println!("{}", lang.best_score(best));
+    }
+    if let Some(average) = stats.average_attempts {
+        println!("{}", lang.average_attempts(average));
+    }
+    println!("{}", lang.current_streak(stats.current_streak));
+    println!("{}", lang.longest_streak(stats.longest_streak));
+
+    println!();
+    println!("{}", lang.attempts_per_game());
+    let widest = stats.histogram.values().copied().max().unwrap_or(0);
+    for (attempts, count) in &stats.histogram {
+        let bar = "#".repeat((*count * 40 / widest.max(1)).max(1) as usize);
+        println!("{attempts:>4} | {bar} {count}");
+    }
+}
+
+fn show_leaderboard(config: &Config) {
+    let lang = config.lang();
+    let records = load_records(config);
+    let boards = stats::leaderboard(&records, LEADERBOARD_SIZE);
+
+    if boards.is_empty() {
+        println!("{}", lang.no_wins());
+        return;
+    }
+
+    let scored = config.mode == GameMode::TimeAttack;
+    for (index, ((min, max), board)) in boards.iter().enumerate() {
+        if index > 0 {
+            println!();
+        }
+        match config.mode {
+            GameMode::Number | GameMode::TimeAttack => {
+                println!("{}", lang.leaderboard_range(*min, *max))
             }
+            GameMode::Word => println!("{}", lang.leaderboard_words()),
+        }
+
+        println!("{}", lang.leaderboard_columns(scored));
+        for (rank, record) in board.iter().enumerate() {
+            let player = match record.score() {
+                Some(score) => format!("{:<16} {score:>5}", record.player),
+                None => format!("{:<16}", record.player),
+            };
+            println!(
+                "{:>4}  {player} {:>8}  {:>6.1}s  {}",
+                rank + 1,
+                record.attempts,
+                record.duration.as_secs_f64(),
+                record.seed
+            );
         }
     }
-}
\ No newline at end of file
+}
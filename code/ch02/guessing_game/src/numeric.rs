@@ -0,0 +1,240 @@
+use crate::guess::GuessError;
+use rand::distributions::uniform::SampleUniform;
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display};
+use std::num::{IntErrorKind, ParseIntError};
+
+/// A primitive type the secret and the guesses can be.
+///
+/// Every integer type and both float types implement it, so one game
+/// shows how each of them parses, overflows and compares.
+pub trait Numeric:
+    Copy + PartialOrd + Display + Debug + SampleUniform + Serialize + 'static
+{
+    /// The type's name as written in Rust, such as `i64`.
+    const NAME: &'static str;
+    const MIN: Self;
+    const MAX: Self;
+    const IS_FLOAT: bool;
+
+    /// Parses trimmed, non-empty input, explaining why it does not fit.
+    fn parse_number(input: &str) -> Result<Self, GuessError<Self>>;
+
+    /// Compares a guess with the secret; floats within `tolerance` are equal.
+    fn compare(self, secret: Self, tolerance: f64) -> Ordering;
+
+    /// How far apart two values are, for warmer/colder hints.
+    fn distance(self, other: Self) -> f64;
+
+    /// How a bound is written in messages.
+    fn bound_text(self) -> String {
+        self.to_string()
+    }
+
+    /// Whether a secret can be picked from `min..=max`. Floats need
+    /// `max - min` to be finite, which `-1e308..=1e308` is not.
+    fn span_is_finite(_min: Self, _max: Self) -> bool {
+        true
+    }
+}
+
+/// `-` followed by digits: a number, just not one an unsigned type can hold.
+fn is_negative_integer(input: &str) -> bool {
+    match input.strip_prefix('-') {
+        Some(digits) => !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()),
+        None => false,
+    }
+}
+
+macro_rules! integer {
+    ($($t:ty),*) => {$(
+        impl Numeric for $t {
+            const NAME: &'static str = stringify!($t);
+            const MIN: $t = <$t>::MIN;
+            const MAX: $t = <$t>::MAX;
+            const IS_FLOAT: bool = false;
+
+            fn parse_number(input: &str) -> Result<$t, GuessError<$t>> {
+                input.parse().map_err(|err: ParseIntError| match err.kind() {
+                    IntErrorKind::PosOverflow => GuessError::Overflow(input.to_string()),
+                    IntErrorKind::NegOverflow => GuessError::Underflow(input.to_string()),
+                    _ if is_negative_integer(input) => GuessError::Negative(input.to_string()),
+                    _ => GuessError::NotANumber(input.to_string()),
+                })
+            }
+
+            fn compare(self, secret: $t, _tolerance: f64) -> Ordering {
+                self.cmp(&secret)
+            }
+
+            fn distance(self, other: $t) -> f64 {
+                self.abs_diff(other) as f64
+            }
+        }
+    )*};
+}
+
+macro_rules! float {
+    ($($t:ty),*) => {$(
+        impl Numeric for $t {
+            const NAME: &'static str = stringify!($t);
+            const MIN: $t = <$t>::MIN;
+            const MAX: $t = <$t>::MAX;
+            const IS_FLOAT: bool = true;
+
+            /// `NaN` is not a number to guess, and anything that rounds to
+            /// infinity is out of the type's range.
+            fn parse_number(input: &str) -> Result<$t, GuessError<$t>> {
+                let value: $t = input
+                    .parse()
+                    .map_err(|_| GuessError::NotANumber(input.to_string()))?;
+
+                if value.is_nan() {
+                    Err(GuessError::NotANumber(input.to_string()))
+                } else if value == <$t>::INFINITY {
+                    Err(GuessError::Overflow(input.to_string()))
+                } else if value == <$t>::NEG_INFINITY {
+                    Err(GuessError::Underflow(input.to_string()))
+                } else {
+                    Ok(value)
+                }
+            }
+
+            fn compare(self, secret: $t, tolerance: f64) -> Ordering {
+                if self.distance(secret) <= tolerance {
+                    Ordering::Equal
+                } else {
+                    self.partial_cmp(&secret).expect("parsed floats are never NaN")
+                }
+            }
+
+            fn distance(self, other: $t) -> f64 {
+                (f64::from(self) - f64::from(other)).abs()
+            }
+
+            /// `1.7976931348623157e308` rather than all 309 digits.
+            fn bound_text(self) -> String {
+                format!("{self:e}")
+            }
+
+            fn span_is_finite(min: $t, max: $t) -> bool {
+                (max - min).is_finite()
+            }
+        }
+    )*};
+}
+
+integer!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+float!(f32, f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn floats_within_the_tolerance_are_equal() {
+        assert_eq!(2.5f64.compare(2.5, 0.0), Ordering::Equal);
+        assert_eq!(2.49f64.compare(2.5, 0.01), Ordering::Equal);
+        assert_eq!(2.52f64.compare(2.5, 0.01), Ordering::Greater);
+        assert_eq!(2.48f64.compare(2.5, 0.01), Ordering::Less);
+        assert_eq!(2.5001f64.compare(2.5, 0.0), Ordering::Greater);
+        assert_eq!(10.0f64.compare(2.5, 100.0), Ordering::Equal);
+
+        // f32 values widen to f64 before the distance is taken.
+        assert_eq!(0.1f32.compare(0.1, 0.0), Ordering::Equal);
+        assert_eq!(0.2f32.compare(0.1, 0.05), Ordering::Greater);
+        assert_eq!(0.1f32.distance(0.2), f64::from(0.2f32) - f64::from(0.1f32));
+    }
+
+    #[test]
+    fn integers_ignore_the_tolerance() {
+        assert_eq!(4u32.compare(5, 10.0), Ordering::Less);
+        assert_eq!((-3i8).compare(-3, 0.0), Ordering::Equal);
+        assert_eq!(u128::MAX.distance(0), u128::MAX as f64);
+        assert_eq!(i8::MIN.distance(i8::MAX), 255.0);
+    }
+
+    #[test]
+    fn integers_explain_why_they_do_not_parse() {
+        assert_eq!(u8::parse_number("255"), Ok(255));
+        assert_eq!(
+            u8::parse_number("256"),
+            Err(GuessError::Overflow(String::from("256")))
+        );
+        assert_eq!(
+            u8::parse_number("-1"),
+            Err(GuessError::Negative(String::from("-1")))
+        );
+        assert_eq!(
+            i8::parse_number("-129"),
+            Err(GuessError::Underflow(String::from("-129")))
+        );
+        assert_eq!(
+            i8::parse_number("-"),
+            Err(GuessError::NotANumber(String::from("-")))
+        );
+        assert_eq!(
+            u32::parse_number("-1.5"),
+            Err(GuessError::NotANumber(String::from("-1.5")))
+        );
+        assert_eq!(
+            i64::parse_number("ten"),
+            Err(GuessError::NotANumber(String::from("ten")))
+        );
+    }
+
+    #[test]
+    fn floats_reject_nan_and_infinity() {
+        for input in ["NaN", "nan", "-nan"] {
+            assert_eq!(
+                f64::parse_number(input),
+                Err(GuessError::NotANumber(input.to_string()))
+            );
+        }
+        for input in ["inf", "infinity", "+inf"] {
+            assert_eq!(
+                f64::parse_number(input),
+                Err(GuessError::Overflow(input.to_string()))
+            );
+        }
+        assert_eq!(
+            f32::parse_number("-inf"),
+            Err(GuessError::Underflow(String::from("-inf")))
+        );
+        assert_eq!(
+            f64::parse_number("1.5.2"),
+            Err(GuessError::NotANumber(String::from("1.5.2")))
+        );
+    }
+
+    #[test]
+    fn floats_out_of_range_overflow() {
+        assert_eq!(
+            f32::parse_number("1e39"),
+            Err(GuessError::Overflow(String::from("1e39")))
+        );
+        assert_eq!(
+            f32::parse_number("-1e39"),
+            Err(GuessError::Underflow(String::from("-1e39")))
+        );
+        assert_eq!(f64::parse_number("1e39"), Ok(1e39));
+        assert_eq!(
+            f64::parse_number("2e308"),
+            Err(GuessError::Overflow(String::from("2e308")))
+        );
+        assert_eq!(f64::parse_number("1e-400"), Ok(0.0));
+    }
+
+    #[test]
+    fn float_spans_must_be_finite() {
+        assert!(f64::span_is_finite(-1e307, 1e307));
+        assert!(!f64::span_is_finite(f64::MIN, f64::MAX));
+        assert!(!f64::span_is_finite(-1e308, 1e308));
+        assert!(f32::span_is_finite(-1e38, 1e38));
+        assert!(!f32::span_is_finite(f32::MIN, f32::MAX));
+        assert!(i128::span_is_finite(i128::MIN, i128::MAX));
+        assert_eq!(f64::MAX.bound_text(), "1.7976931348623157e308");
+        assert_eq!(u8::MAX.bound_text(), "255");
+    }
+}
@@ -0,0 +1,403 @@
+use crate::{Config, Game, GuessError, GuessSource, Hint, Outcome, Reporter, SeedMode};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// One line of a recorded session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Entry {
+    /// Always the first line: everything needed to rebuild the game.
+    Session {
+        seed: u64,
+        min: u32,
+        max: u32,
+        max_attempts: Option<u32>,
+        strict: bool,
+        hints: bool,
+    },
+    Guess {
+        timestamp: u64,
+        input: String,
+        guess: Option<u32>,
+        feedback: Feedback,
+    },
+    Finish {
+        timestamp: u64,
+        outcome: Outcome,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "error", rename_all = "snake_case")]
+pub enum Feedback {
+    TooSmall,
+    TooBig,
+    Correct,
+    Invalid(String),
+}
+
+impl Entry {
+    pub fn session(config: &Config, seed: u64) -> Entry {
+        Entry::Session {
+            seed,
+            min: *config.range.start(),
+            max: *config.range.end(),
+            max_attempts: config.max_attempts,
+            strict: config.strict,
+            hints: config.hints,
+        }
+    }
+
+    /// The same entry with its timestamp zeroed, for comparing replays.
+    fn untimed(&self) -> Entry {
+        let mut entry = self.clone();
+        match &mut entry {
+            Entry::Guess { timestamp, .. } | Entry::Finish { timestamp, .. } => *timestamp = 0,
+            Entry::Session { .. } => {}
+        }
+        entry
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Wraps another reporter and writes every event as a JSON line.
+///
+/// Write errors do not interrupt the game; the first one is kept and
+/// returned by [`Recorder::finish_recording`].
+pub struct Recorder<P, W> {
+    inner: P,
+    writer: W,
+    input: Option<(u64, String)>,
+    guess: Option<u32>,
+    error: Option<io::Error>,
+}
+
+impl<P: Reporter, W: Write> Recorder<P, W> {
+    pub fn new(inner: P, writer: W, session: Entry) -> Recorder<P, W> {
+        let mut recorder = Recorder {
+            inner,
+            writer,
+            input: None,
+            guess: None,
+            error: None,
+        };
+        recorder.write(&session);
+        recorder
+    }
+
+    pub fn finish_recording(mut self) -> io::Result<W> {
+        if let Some(err) = self.error.take() {
+            return Err(err);
+        }
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    fn write(&mut self, entry: &Entry) {
+        if self.error.is_some() {
+            return;
+        }
+
+        let result = serde_json::to_writer(&mut self.writer, entry)
+            .map_err(io::Error::from)
+            .and_then(|()| writeln!(self.writer));
+        if let Err(err) = result {
+            self.error = Some(err);
+        }
+    }
+
+    fn write_guess(&mut self, feedback: Feedback) {
+        let (timestamp, input) = self.input.take().unwrap_or_else(|| (now(), String::new()));
+        let entry = Entry::Guess {
+            timestamp,
+            input,
+            guess: self.guess.take(),
+            feedback,
+        };
+        self.write(&entry);
+    }
+}
+
+impl<P: Reporter, W: Write> Reporter for Recorder<P, W> {
+    fn start(&mut self) {
+        self.inner.start();
+    }
+
+    fn prompt(&mut self) {
+        self.inner.prompt();
+    }
+
+    fn input(&mut self, line: &str) {
+        self.input = Some((now(), line.trim_end_matches(['\r', '\n']).to_string()));
+        self.inner.input(line);
+    }
+
+    fn invalid(&mut self, error: &GuessError) {
+        self.write_guess(Feedback::Invalid(error.to_string()));
+        self.inner.invalid(error);
+    }
+
+    fn guessed(&mut self, guess: u32) {
+        self.guess = Some(guess);
+        self.inner.guessed(guess);
+    }
+
+    fn feedback(&mut self, ordering: Ordering) {
+        self.write_guess(match ordering {
+            Ordering::Less => Feedback::TooSmall,
+            Ordering::Greater => Feedback::TooBig,
+            Ordering::Equal => Feedback::Correct,
+        });
+        self.inner.feedback(ordering);
+    }
+
+    fn attempts(&mut self, used: u32) {
+        self.inner.attempts(used);
+    }
+
+    fn hint(&mut self, hint: &Hint) {
+        self.inner.hint(hint);
+    }
+
+    fn finish(&mut self, outcome: &Outcome) {
+        let entry = Entry::Finish {
+            timestamp: now(),
+            outcome: *outcome,
+        };
+        self.write(&entry);
+        self.inner.finish(outcome);
+    }
+}
+
+/// Reads a whole recording, one entry per non-empty line.
+pub fn read_log(reader: impl BufRead) -> Result<Vec<Entry>, ReplayError> {
+    let mut entries = Vec::new();
+
+    for (number, line) in reader.lines().enumerate() {
+        let line = line.map_err(|err| ReplayError::Io(err.to_string()))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let entry = serde_json::from_str(&line).map_err(|err| ReplayError::Parse {
+            line: number + 1,
+            message: err.to_string(),
+        })?;
+        entries.push(entry);
+    }
+
+    Ok(entries)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplayError {
+    Io(String),
+    Parse {
+        line: usize,
+        message: String,
+    },
+    MissingSession,
+    /// Entry `index` (0-based, after the session line) came out differently.
+    Diverged {
+        index: usize,
+        expected: Option<Box<Entry>>,
+        actual: Option<Box<Entry>>,
+    },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::Io(message) => write!(f, "could not read the log: {message}"),
+            ReplayError::Parse { line, message } => write!(f, "line {line}: {message}"),
+            ReplayError::MissingSession => write!(f, "the log does not start with a session"),
+            ReplayError::Diverged {
+                index,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "event {} differs: recorded {expected:?}, replayed {actual:?}",
+                index + 1
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+struct Script {
+    inputs: std::vec::IntoIter<String>,
+}
+
+impl GuessSource for Script {
+    fn next_guess(&mut self) -> Option<String> {
+        self.inputs.next()
+    }
+}
+
+struct Silent;
+
+impl Reporter for Silent {
+    fn start(&mut self) {}
+    fn prompt(&mut self) {}
+    fn invalid(&mut self, _error: &GuessError) {}
+    fn guessed(&mut self, _guess: u32) {}
+    fn feedback(&mut self, _ordering: Ordering) {}
+    fn finish(&mut self, _outcome: &Outcome) {}
+}
+
+/// Feeds the recorded inputs back through a game rebuilt from the
+/// session line and checks that every event comes out the same.
+pub fn replay(entries: &[Entry]) -> Result<Outcome, ReplayError> {
+    let (session, recorded) = entries.split_first().ok_or(ReplayError::MissingSession)?;
+
+    let (seed, config) = match *session {
+        Entry::Session {
+            seed,
+            min,
+            max,
+            max_attempts,
+            strict,
+            hints,
+        } => (
+            seed,
+            Config {
+                range: min..=max,
+                max_attempts,
+                seed: SeedMode::Fixed(seed),
+                strict,
+                hints,
+                ..Config::default()
+            },
+        ),
+        _ => return Err(ReplayError::MissingSession),
+    };
+
+    let inputs: Vec<String> = recorded
+        .iter()
+        .filter_map(|entry| match entry {
+            Entry::Guess { input, .. } => Some(input.clone()),
+            _ => None,
+        })
+        .collect();
+
+    let mut game = Game::from_config(&mut StdRng::seed_from_u64(seed), &config);
+    let mut source = Script {
+        inputs: inputs.into_iter(),
+    };
+    let mut recorder = Recorder::new(Silent, Vec::new(), session.clone());
+    let outcome = crate::play(&mut game, &mut source, &mut recorder);
+
+    let log = recorder
+        .finish_recording()
+        .map_err(|err| ReplayError::Io(err.to_string()))?;
+    let replayed = read_log(log.as_slice())?;
+
+    let expected: Vec<Entry> = recorded.iter().map(Entry::untimed).collect();
+    let actual: Vec<Entry> = replayed[1..].iter().map(Entry::untimed).collect();
+
+    for index in 0..expected.len().max(actual.len()) {
+        if expected.get(index) != actual.get(index) {
+            return Err(ReplayError::Diverged {
+                index,
+                expected: expected.get(index).cloned().map(Box::new),
+                actual: actual.get(index).cloned().map(Box::new),
+            });
+        }
+    }
+
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WON: &str = include_str!("../tests/fixtures/won.jsonl");
+    const LOST: &str = include_str!("../tests/fixtures/lost.jsonl");
+    const QUIT: &str = include_str!("../tests/fixtures/quit.jsonl");
+
+    fn entries(log: &str) -> Vec<Entry> {
+        read_log(log.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn replays_recorded_games() {
+        assert_eq!(replay(&entries(WON)), Ok(Outcome::Won { attempts: 4 }));
+        assert_eq!(
+            replay(&entries(LOST)),
+            Ok(Outcome::Lost {
+                attempts: 3,
+                secret: 42
+            })
+        );
+        assert_eq!(replay(&entries(QUIT)), Ok(Outcome::Quit { attempts: 1 }));
+    }
+
+    #[test]
+    fn tampered_feedback_diverges() {
+        let tampered = WON.replacen(r#""kind":"too_small""#, r#""kind":"too_big""#, 1);
+
+        match replay(&entries(&tampered)) {
+            Err(ReplayError::Diverged {
+                index,
+                expected,
+                actual,
+            }) => {
+                assert_eq!(index, 3);
+                assert_ne!(expected, actual);
+            }
+            other => panic!("expected a divergence, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn tampered_seed_diverges() {
+        let tampered = LOST.replacen(r#""seed":7"#, r#""seed":8"#, 1);
+
+        assert!(matches!(
+            replay(&entries(&tampered)),
+            Err(ReplayError::Diverged { .. })
+        ));
+    }
+
+    #[test]
+    fn dropped_finish_diverges() {
+        let mut log = entries(QUIT);
+        log.pop();
+
+        assert_eq!(
+            replay(&log),
+            Err(ReplayError::Diverged {
+                index: 1,
+                expected: None,
+                actual: Some(Box::new(Entry::Finish {
+                    timestamp: 0,
+                    outcome: Outcome::Quit { attempts: 1 },
+                })),
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_logs_without_a_session() {
+        assert_eq!(replay(&[]), Err(ReplayError::MissingSession));
+        assert_eq!(replay(&entries(WON)[1..]), Err(ReplayError::MissingSession));
+        assert!(matches!(
+            read_log("{\"event\":\"nope\"}".as_bytes()),
+            Err(ReplayError::Parse { line: 1, .. })
+        ));
+    }
+}
@@ -0,0 +1,255 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+
+/// The player's reply to one of the computer's guesses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Answer {
+    Higher,
+    Lower,
+    Correct,
+}
+
+impl Answer {
+    /// Accepts `higher`/`lower`/`correct` or their first letters.
+    pub fn parse(input: &str) -> Option<Answer> {
+        match input.trim().to_lowercase().as_str() {
+            "h" | "higher" | "+" => Some(Answer::Higher),
+            "l" | "lower" | "-" => Some(Answer::Lower),
+            "c" | "correct" | "=" => Some(Answer::Correct),
+            _ => None,
+        }
+    }
+}
+
+/// The answers left no number that fits all of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cheating {
+    pub guess: u32,
+    pub answer: Answer,
+    /// The candidates that were still possible before the answer.
+    pub low: u32,
+    pub high: u32,
+}
+
+impl fmt::Display for Cheating {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let direction = match self.answer {
+            Answer::Higher => "higher than",
+            Answer::Lower => "lower than",
+            Answer::Correct => "equal to",
+        };
+
+        write!(f, "Your number cannot be {direction} {}: ", self.guess)?;
+        if self.low == self.high {
+            write!(f, "your earlier answers only leave {}.", self.low)
+        } else {
+            write!(
+                f,
+                "your earlier answers only leave {} to {}.",
+                self.low, self.high
+            )
+        }
+    }
+}
+
+/// Finds the player's number by halving the candidate range.
+#[derive(Debug, Clone)]
+pub struct Guesser {
+    low: u32,
+    high: u32,
+    guesses: u32,
+    found: Option<u32>,
+}
+
+impl Guesser {
+    pub fn new(range: RangeInclusive<u32>) -> Guesser {
+        Guesser {
+            low: *range.start(),
+            high: *range.end(),
+            guesses: 0,
+            found: None,
+        }
+    }
+
+    /// The numbers still consistent with every answer so far.
+    pub fn remaining(&self) -> RangeInclusive<u32> {
+        self.low..=self.high
+    }
+
+    pub fn guesses(&self) -> u32 {
+        self.guesses
+    }
+
+    pub fn found(&self) -> Option<u32> {
+        self.found
+    }
+
+    /// The midpoint of the remaining candidates.
+    pub fn next_guess(&self) -> u32 {
+        self.low + (self.high - self.low) / 2
+    }
+
+    /// Narrows the candidates after the player answered `guess`.
+    pub fn answer(&mut self, guess: u32, answer: Answer) -> Result<(), Cheating> {
+        let cheating = Cheating {
+            guess,
+            answer,
+            low: self.low,
+            high: self.high,
+        };
+
+        if !self.remaining().contains(&guess) {
+            return Err(cheating);
+        }
+        self.guesses += 1;
+
+        match answer {
+            Answer::Correct => self.found = Some(guess),
+            Answer::Higher => {
+                if guess == self.high {
+                    return Err(cheating);
+                }
+                self.low = guess + 1;
+            }
+            Answer::Lower => {
+                if guess == self.low {
+                    return Err(cheating);
+                }
+                self.high = guess - 1;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The most guesses binary search can need for `range`: ⌊log2 n⌋ + 1.
+pub fn worst_case_guesses(range: &RangeInclusive<u32>) -> u32 {
+    let size = u64::from(*range.end()) - u64::from(*range.start()) + 1;
+
+    size.ilog2() + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Plays honestly for `secret` and returns how many guesses it took.
+    fn find(range: RangeInclusive<u32>, secret: u32) -> u32 {
+        let mut guesser = Guesser::new(range);
+
+        while guesser.found().is_none() {
+            let guess = guesser.next_guess();
+            let answer = match secret.cmp(&guess) {
+                std::cmp::Ordering::Less => Answer::Lower,
+                std::cmp::Ordering::Greater => Answer::Higher,
+                std::cmp::Ordering::Equal => Answer::Correct,
+            };
+            guesser.answer(guess, answer).unwrap();
+        }
+
+        assert_eq!(guesser.found(), Some(secret));
+        guesser.guesses()
+    }
+
+    #[test]
+    fn parses_answers() {
+        assert_eq!(Answer::parse(" Higher\n"), Some(Answer::Higher));
+        assert_eq!(Answer::parse("l"), Some(Answer::Lower));
+        assert_eq!(Answer::parse("="), Some(Answer::Correct));
+        assert_eq!(Answer::parse("maybe"), None);
+    }
+
+    #[test]
+    fn finds_every_number_within_the_worst_case() {
+        for range in [1..=1, 1..=10, 1..=100, 5..=17] {
+            let worst = worst_case_guesses(&range);
+            let most = range
+                .clone()
+                .map(|secret| find(range.clone(), secret))
+                .max();
+            assert_eq!(most, Some(worst), "{range:?}");
+        }
+    }
+
+    #[test]
+    fn finds_the_ends_of_the_range() {
+        assert_eq!(find(1..=100, 1), 6);
+        assert_eq!(find(1..=100, 100), 7);
+
+        let full = 0..=u32::MAX;
+        assert_eq!(worst_case_guesses(&full), 33);
+        assert!(find(full.clone(), 0) <= 33);
+        assert!(find(full.clone(), u32::MAX) <= 33);
+    }
+
+    #[test]
+    fn worst_case_is_floor_log2_plus_one() {
+        assert_eq!(worst_case_guesses(&(1..=1)), 1);
+        assert_eq!(worst_case_guesses(&(1..=2)), 2);
+        assert_eq!(worst_case_guesses(&(1..=7)), 3);
+        assert_eq!(worst_case_guesses(&(1..=8)), 4);
+        assert_eq!(worst_case_guesses(&(1..=100)), 7);
+    }
+
+    #[test]
+    fn reports_contradictory_answers_as_cheating() {
+        let mut guesser = Guesser::new(1..=100);
+        guesser.answer(50, Answer::Higher).unwrap();
+        assert_eq!(guesser.remaining(), 51..=100);
+
+        let cheating = guesser.answer(51, Answer::Lower).unwrap_err();
+        assert_eq!(
+            cheating,
+            Cheating {
+                guess: 51,
+                answer: Answer::Lower,
+                low: 51,
+                high: 100
+            }
+        );
+        assert_eq!(
+            cheating.to_string(),
+            "Your number cannot be lower than 51: your earlier answers only leave 51 to 100."
+        );
+        // A rejected answer narrows nothing.
+        assert_eq!(guesser.remaining(), 51..=100);
+    }
+
+    #[test]
+    fn cannot_go_past_the_ends() {
+        let mut guesser = Guesser::new(1..=100);
+        assert!(guesser.answer(100, Answer::Higher).is_err());
+        assert!(guesser.answer(1, Answer::Lower).is_err());
+
+        let mut guesser = Guesser::new(1..=100);
+        guesser.answer(50, Answer::Lower).unwrap();
+        guesser.answer(25, Answer::Higher).unwrap();
+        guesser.answer(37, Answer::Lower).unwrap();
+        guesser.answer(31, Answer::Higher).unwrap();
+        guesser.answer(34, Answer::Lower).unwrap();
+        guesser.answer(32, Answer::Higher).unwrap();
+        assert_eq!(guesser.remaining(), 33..=33);
+
+        let cheating = guesser.answer(33, Answer::Higher).unwrap_err();
+        assert_eq!(
+            cheating.to_string(),
+            "Your number cannot be higher than 33: your earlier answers only leave 33."
+        );
+        assert_eq!(guesser.answer(33, Answer::Correct), Ok(()));
+        assert_eq!(guesser.found(), Some(33));
+    }
+
+    #[test]
+    fn rejects_guesses_already_ruled_out() {
+        let mut guesser = Guesser::new(1..=10);
+        guesser.answer(5, Answer::Lower).unwrap();
+
+        let cheating = guesser.answer(7, Answer::Correct).unwrap_err();
+        assert_eq!((cheating.low, cheating.high), (1, 4));
+        assert_eq!(
+            cheating.to_string(),
+            "Your number cannot be equal to 7: your earlier answers only leave 1 to 4."
+        );
+    }
+}
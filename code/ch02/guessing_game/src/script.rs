@@ -0,0 +1,248 @@
+use crate::{GuessError, GuessSource, Number, Numeric, Outcome, Reporter};
+use serde::{Serialize, Serializer};
+use std::cmp::Ordering;
+use std::io::{self, BufRead, Write};
+
+/// Exit statuses, so scripts can tell how a game ended.
+pub const EXIT_LOST: i32 = 1;
+pub const EXIT_USAGE: i32 = 2;
+/// The input ran out before the game was won or lost.
+pub const EXIT_UNFINISHED: i32 = 3;
+
+/// The status the process exits with once `outcome` is known.
+pub fn exit_code<T>(outcome: &Outcome<T>) -> i32 {
+    match outcome {
+        Outcome::Won { .. } => 0,
+        Outcome::Lost { .. } => EXIT_LOST,
+        Outcome::Quit { .. } => EXIT_UNFINISHED,
+    }
+}
+
+/// Guesses read one line at a time from a file, a pipe or the terminal.
+///
+/// End of input and read errors, such as a line that is not UTF-8, both
+/// end the game instead of being retried.
+pub struct Lines<R> {
+    reader: R,
+}
+
+impl<R: BufRead> Lines<R> {
+    pub fn new(reader: R) -> Lines<R> {
+        Lines { reader }
+    }
+}
+
+impl<R: BufRead> GuessSource for Lines<R> {
+    fn next_guess(&mut self) -> Option<String> {
+        let mut line = String::new();
+
+        match self.reader.read_line(&mut line) {
+            Ok(0) | Err(_) => None,
+            Ok(_) => Some(line),
+        }
+    }
+}
+
+/// One line of input as it appears in the JSON report.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Turn<T> {
+    Guess {
+        guess: T,
+        /// `Less`, `Greater` or `Equal`, comparing the guess with the secret.
+        #[serde(serialize_with = "serialize_ordering")]
+        ordering: Ordering,
+    },
+    Invalid {
+        input: String,
+        error: String,
+    },
+    TimedOut,
+}
+
+fn serialize_ordering<S: Serializer>(
+    ordering: &Ordering,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(match ordering {
+        Ordering::Less => "Less",
+        Ordering::Greater => "Greater",
+        Ordering::Equal => "Equal",
+    })
+}
+
+/// Everything a grading script needs from one game.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Report<T> {
+    pub seed: u64,
+    pub guesses: Vec<Turn<T>>,
+    pub attempts: u32,
+    pub outcome: Option<Outcome<T>>,
+}
+
+/// Prints a single JSON `Report` once the game ends, and nothing before.
+pub struct JsonReporter<T, W: Write = io::Stdout> {
+    out: W,
+    report: Report<T>,
+    input: String,
+    guess: Option<T>,
+}
+
+impl<T: Numeric> JsonReporter<T> {
+    pub fn stdout(seed: u64) -> JsonReporter<T> {
+        JsonReporter::new(io::stdout(), seed)
+    }
+}
+
+impl<T: Numeric, W: Write> JsonReporter<T, W> {
+    pub fn new(out: W, seed: u64) -> JsonReporter<T, W> {
+        JsonReporter {
+            out,
+            report: Report {
+                seed,
+                guesses: Vec::new(),
+                attempts: 0,
+                outcome: None,
+            },
+            input: String::new(),
+            guess: None,
+        }
+    }
+
+    pub fn report(&self) -> &Report<T> {
+        &self.report
+    }
+}
+
+impl<T: Numeric, W: Write> Reporter<Number<T>> for JsonReporter<T, W> {
+    fn start(&mut self) {}
+
+    fn prompt(&mut self) {}
+
+    fn input(&mut self, line: &str) {
+        self.input = line.trim().to_string();
+    }
+
+    fn invalid(&mut self, error: &GuessError<T>) {
+        self.report.guesses.push(Turn::Invalid {
+            input: self.input.clone(),
+            error: error.to_string(),
+        });
+    }
+
+    fn guessed(&mut self, guess: T) {
+        self.guess = Some(guess);
+    }
+
+    fn feedback(&mut self, ordering: Ordering) {
+        if let Some(guess) = self.guess.take() {
+            self.report.guesses.push(Turn::Guess { guess, ordering });
+        }
+    }
+
+    fn timed_out(&mut self) {
+        self.report.guesses.push(Turn::TimedOut);
+    }
+
+    fn finish(&mut self, outcome: &Outcome<T>) {
+        self.report.attempts = outcome.attempts();
+        self.report.outcome = Some(*outcome);
+
+        let written = serde_json::to_writer(&mut self.out, &self.report)
+            .map_err(io::Error::from)
+            .and_then(|()| writeln!(self.out))
+            .and_then(|()| self.out.flush());
+        if let Err(err) = written {
+            eprintln!("Could not write the report: {err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Game;
+    use serde_json::{json, Value};
+
+    fn report<T: Numeric>(game: &mut Game<Number<T>>, input: &str) -> (Outcome<T>, Value) {
+        let mut out = Vec::new();
+        let outcome = crate::play(
+            game,
+            &mut Lines::new(input.as_bytes()),
+            &mut JsonReporter::new(&mut out, 7),
+        );
+
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(text.lines().count(), 1, "{text}");
+        (outcome, serde_json::from_str(&text).unwrap())
+    }
+
+    #[test]
+    fn reports_a_won_game() {
+        let (outcome, report) = report(&mut Game::new(42), "50\nfifty\n42\n");
+
+        assert_eq!(outcome, Outcome::Won { attempts: 2 });
+        assert_eq!(
+            report,
+            json!({
+                "seed": 7,
+                "guesses": [
+                    { "kind": "guess", "guess": 50, "ordering": "Greater" },
+                    {
+                        "kind": "invalid",
+                        "input": "fifty",
+                        "error": GuessError::<u32>::NotANumber(String::from("fifty")).to_string(),
+                    },
+                    { "kind": "guess", "guess": 42, "ordering": "Equal" },
+                ],
+                "attempts": 2,
+                "outcome": { "result": "won", "attempts": 2 },
+            })
+        );
+    }
+
+    #[test]
+    fn reports_a_lost_game_with_its_secret() {
+        let mut game = Game::new(42).with_max_attempts(2);
+        let (_, report) = report(&mut game, "10\n90\n");
+
+        assert_eq!(report["attempts"], 2);
+        assert_eq!(report["guesses"][0]["ordering"], "Less");
+        assert_eq!(report["guesses"][1]["ordering"], "Greater");
+        assert_eq!(
+            report["outcome"],
+            json!({ "result": "lost", "attempts": 2, "secret": 42 })
+        );
+    }
+
+    #[test]
+    fn reports_unfinished_input_and_float_guesses() {
+        let mut game = Game::with_secret(Number::new(1.5f64, 0.0..=10.0)).with_tolerance(0.1);
+        let (outcome, report) = report(&mut game, "2.25\n");
+
+        assert_eq!(outcome, Outcome::Quit { attempts: 1 });
+        assert_eq!(report["guesses"][0]["guess"], 2.25);
+        assert_eq!(
+            report["outcome"],
+            json!({ "result": "quit", "attempts": 1 })
+        );
+    }
+
+    #[test]
+    fn maps_each_outcome_to_an_exit_code() {
+        assert_eq!(exit_code(&Outcome::<u32>::Won { attempts: 3 }), 0);
+        assert_eq!(
+            exit_code(&Outcome::Lost {
+                attempts: 3,
+                secret: 7
+            }),
+            EXIT_LOST
+        );
+        assert_eq!(
+            exit_code(&Outcome::<u32>::Quit { attempts: 0 }),
+            EXIT_UNFINISHED
+        );
+
+        assert_eq!((EXIT_LOST, EXIT_USAGE, EXIT_UNFINISHED), (1, 2, 3));
+    }
+}
@@ -0,0 +1,122 @@
+use crate::guess::{self, GuessError};
+use crate::hint::{Hint, Hinter};
+use crate::numeric::Numeric;
+use std::cmp::Ordering;
+use std::fmt::Debug;
+use std::ops::RangeInclusive;
+
+/// Something the player is trying to guess.
+///
+/// The game loop only ever talks to a secret through this trait, so the
+/// number game and the word game share attempts, limits and reporting.
+pub trait Secret {
+    type Guess: Clone;
+    type Feedback: Clone;
+    type Error;
+    /// What is revealed when the player loses.
+    type Answer: Clone + Debug;
+
+    /// Turns one line of input into a guess, without changing any state.
+    fn parse(&self, input: &str) -> Result<Self::Guess, Self::Error>;
+
+    /// Scores a guess. Secrets with extra rules may still reject it here.
+    fn check(&mut self, guess: &Self::Guess) -> Result<Self::Feedback, Self::Error>;
+
+    fn is_solved(&self, feedback: &Self::Feedback) -> bool;
+
+    fn answer(&self) -> Self::Answer;
+
+    /// A warmer/colder hint for the latest miss, for secrets that have one.
+    fn hint(&self) -> Option<Hint> {
+        None
+    }
+}
+
+/// The classic secret: a number compared with the guess.
+///
+/// `T` can be any `Numeric` type. Floats count a guess as correct once it
+/// is within the tolerance, which integers ignore.
+#[derive(Debug, Clone)]
+pub struct Number<T = u32> {
+    value: T,
+    range: RangeInclusive<T>,
+    tolerance: f64,
+    hinter: Option<Hinter>,
+    last_hint: Option<Hint>,
+}
+
+impl<T: Numeric> Number<T> {
+    pub fn new(value: T, range: RangeInclusive<T>) -> Number<T> {
+        Number {
+            value,
+            range,
+            tolerance: 0.0,
+            hinter: None,
+            last_hint: None,
+        }
+    }
+
+    pub fn value(&self) -> T {
+        self.value
+    }
+
+    pub fn range(&self) -> &RangeInclusive<T> {
+        &self.range
+    }
+
+    pub fn tolerance(&self) -> f64 {
+        self.tolerance
+    }
+
+    pub fn set_range(&mut self, range: RangeInclusive<T>) {
+        if self.hinter.is_some() {
+            self.hinter = Some(Hinter::new(&range));
+        }
+        self.range = range;
+    }
+
+    pub fn set_hints(&mut self, hints: bool) {
+        self.hinter = hints.then(|| Hinter::new(&self.range));
+    }
+
+    pub fn set_tolerance(&mut self, tolerance: f64) {
+        self.tolerance = tolerance;
+    }
+}
+
+impl<T: Numeric> Secret for Number<T> {
+    type Guess = T;
+    type Feedback = Ordering;
+    type Error = GuessError<T>;
+    type Answer = T;
+
+    fn parse(&self, input: &str) -> Result<T, GuessError<T>> {
+        guess::parse_guess(input, &self.range)
+    }
+
+    fn check(&mut self, guess: &T) -> Result<Ordering, GuessError<T>> {
+        let ordering = guess.compare(self.value, self.tolerance);
+
+        self.last_hint = match ordering {
+            Ordering::Equal => None,
+            _ => self
+                .hinter
+                .as_mut()
+                .map(|hinter| hinter.hint(*guess, self.value)),
+        };
+
+        Ok(ordering)
+    }
+
+    fn is_solved(&self, ordering: &Ordering) -> bool {
+        *ordering == Ordering::Equal
+    }
+
+    fn answer(&self) -> T {
+        self.value
+    }
+
+    fn hint(&self) -> Option<Hint> {
+        self.last_hint
+    }
+}
@@ -0,0 +1,261 @@
+use crate::{guess, Game, Lang};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::ops::RangeInclusive;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// How long a message to one player may take before they are dropped.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// A shared secret that every connected player races to guess.
+pub struct Server {
+    range: RangeInclusive<u32>,
+    lang: Lang,
+    state: Mutex<State>,
+}
+
+struct State {
+    rng: StdRng,
+    round: u32,
+    game: Game,
+    players: HashMap<usize, Player>,
+}
+
+struct Player {
+    name: String,
+    stream: TcpStream,
+}
+
+impl Server {
+    pub fn new(range: RangeInclusive<u32>, seed: u64) -> Server {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let game = Game::with_rng(&mut rng, range.clone());
+
+        Server {
+            range,
+            lang: Lang::En,
+            state: Mutex::new(State {
+                rng,
+                round: 1,
+                game,
+                players: HashMap::new(),
+            }),
+        }
+    }
+
+    /// The language every player is spoken to in, and the server logs in.
+    pub fn with_lang(mut self, lang: Lang) -> Server {
+        self.lang = lang;
+        self
+    }
+
+    /// Accepts players until the listener fails, one thread each.
+    pub fn run(self, listener: TcpListener) -> io::Result<()> {
+        let server = Arc::new(self);
+
+        for (id, stream) in listener.incoming().enumerate() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(err) => {
+                    eprintln!("{}", server.lang.accept_failed(err));
+                    continue;
+                }
+            };
+
+            let server = Arc::clone(&server);
+            thread::spawn(move || {
+                if let Err(err) = server.handle(id, stream) {
+                    eprintln!("{}", server.lang.player_disconnected(id, err));
+                }
+                server.leave(id);
+            });
+        }
+
+        Ok(())
+    }
+
+    fn handle(&self, id: usize, stream: TcpStream) -> io::Result<()> {
+        stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+        let mut writer = stream.try_clone()?;
+        let mut lines = BufReader::new(stream.try_clone()?).lines();
+
+        writeln!(writer, "{}", self.lang.server_welcome())?;
+        let name = match lines.next() {
+            Some(line) => line?.trim().to_string(),
+            None => return Ok(()),
+        };
+        let name = if name.is_empty() {
+            format!("player{id}")
+        } else {
+            name
+        };
+
+        let round = self.join(id, &name, stream);
+        writeln!(
+            writer,
+            "{}",
+            self.lang.server_greeting(&name, round, &self.range)
+        )?;
+
+        loop {
+            write!(writer, "> ")?;
+            writer.flush()?;
+
+            let line = match lines.next() {
+                Some(line) => line?,
+                None => return Ok(()),
+            };
+
+            let guess = match guess::parse_guess(&line, &self.range) {
+                Ok(guess) => guess,
+                Err(err) => {
+                    writeln!(writer, "{}", self.lang.guess_error(&err))?;
+                    continue;
+                }
+            };
+
+            match self.guess(&name, guess) {
+                Ordering::Less => writeln!(writer, "{}", self.lang.too_small())?,
+                Ordering::Greater => writeln!(writer, "{}", self.lang.too_big())?,
+                Ordering::Equal => {}
+            }
+        }
+    }
+
+    fn join(&self, id: usize, name: &str, stream: TcpStream) -> u32 {
+        let mut state = self.state.lock().unwrap();
+
+        state.broadcast(self.lang, &self.lang.player_joined(name));
+        state.players.insert(
+            id,
+            Player {
+                name: name.to_string(),
+                stream,
+            },
+        );
+        println!("{}", self.lang.players_log(name, true, state.players.len()));
+
+        state.round
+    }
+
+    fn leave(&self, id: usize) {
+        let mut state = self.state.lock().unwrap();
+
+        if let Some(player) = state.players.remove(&id) {
+            let playing = state.players.len();
+            println!("{}", self.lang.players_log(&player.name, false, playing));
+            state.broadcast(self.lang, &self.lang.player_left(&player.name));
+        }
+    }
+
+    /// Compares against the current round, starting a new one on a win.
+    fn guess(&self, name: &str, guess: u32) -> Ordering {
+        let mut state = self.state.lock().unwrap();
+
+        let ordering = state.game.guess(guess);
+        if ordering == Ordering::Equal {
+            let finished = state.round;
+            state.round += 1;
+            state.game = Game::with_rng(&mut state.rng, self.range.clone());
+
+            println!("{}", self.lang.round_won_log(name, guess, finished));
+            state.broadcast(self.lang, &self.lang.round_won(name, guess, finished));
+        }
+
+        ordering
+    }
+}
+
+impl State {
+    /// Sends `message` to everyone. Players whose write fails or times out
+    /// are dropped, so one who stops reading cannot stall the others.
+    fn broadcast(&mut self, lang: Lang, message: &str) {
+        self.players.retain(|_, player| {
+            if writeln!(player.stream, "{message}").is_ok() {
+                return true;
+            }
+
+            println!("{}", lang.player_dropped_log(&player.name));
+            // Ends their connection too, so their thread stops reading.
+            let _ = player.stream.shutdown(Shutdown::Both);
+            false
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Lines;
+    use std::net::SocketAddr;
+
+    struct Client {
+        stream: TcpStream,
+        lines: Lines<BufReader<TcpStream>>,
+    }
+
+    impl Client {
+        fn connect(addr: SocketAddr, name: &str) -> Client {
+            let stream = TcpStream::connect(addr).unwrap();
+            stream
+                .set_read_timeout(Some(Duration::from_secs(5)))
+                .unwrap();
+            let lines = BufReader::new(stream.try_clone().unwrap()).lines();
+            let mut client = Client { stream, lines };
+
+            assert!(client.line().starts_with("Welcome"));
+            client.send(name);
+            assert!(client.line().starts_with(&format!("Hi {name}! Round 1")));
+            client
+        }
+
+        fn send(&mut self, line: &str) {
+            writeln!(self.stream, "{line}").unwrap();
+        }
+
+        /// The next line, without the prompts in front of it.
+        fn line(&mut self) -> String {
+            let line = self.lines.next().unwrap().unwrap();
+            line.trim_start_matches("> ").to_string()
+        }
+    }
+
+    #[test]
+    fn players_race_for_the_same_secret() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = Server::new(1..=100, 9);
+        thread::spawn(move || server.run(listener));
+
+        let secret = Game::with_rng(&mut StdRng::seed_from_u64(9), 1..=100).secret();
+        assert!(
+            (2..100).contains(&secret),
+            "seed 9 should leave room around {secret}"
+        );
+
+        let mut ann = Client::connect(addr, "ann");
+        let mut bob = Client::connect(addr, "bob");
+        assert_eq!(ann.line(), "bob joined the game.");
+
+        ann.send(&(secret + 1).to_string());
+        assert_eq!(ann.line(), "Too big!");
+        bob.send("0");
+        assert_eq!(bob.line(), "0 is outside the range 1 to 100.");
+        bob.send(&(secret - 1).to_string());
+        assert_eq!(bob.line(), "Too small!");
+
+        bob.send(&secret.to_string());
+        let won = format!("bob guessed {secret} and wins round 1! Round 2 starts now.");
+        assert_eq!(bob.line(), won);
+        assert_eq!(ann.line(), won);
+
+        drop(bob);
+        assert_eq!(ann.line(), "bob left the game.");
+    }
+}
@@ -0,0 +1,228 @@
+use crate::Game;
+use rand::rngs::StdRng;
+use rand::{Rng, RngCore, SeedableRng};
+use std::cmp::Ordering;
+use std::ops::RangeInclusive;
+
+/// Picks the next guess from what the feedback has ruled out so far.
+///
+/// `low..=high` is every number still possible; guesses outside it are
+/// clamped, so a strategy can never stall the game.
+pub trait Strategy {
+    fn name(&self) -> &str;
+    fn next_guess(&mut self, low: u32, high: u32, rng: &mut dyn RngCore) -> u32;
+}
+
+pub struct BinarySearch;
+
+impl Strategy for BinarySearch {
+    fn name(&self) -> &str {
+        "binary"
+    }
+
+    fn next_guess(&mut self, low: u32, high: u32, _rng: &mut dyn RngCore) -> u32 {
+        low + (high - low) / 2
+    }
+}
+
+pub struct RandomGuess;
+
+impl Strategy for RandomGuess {
+    fn name(&self) -> &str {
+        "random"
+    }
+
+    fn next_guess(&mut self, low: u32, high: u32, rng: &mut dyn RngCore) -> u32 {
+        rng.gen_range(low..=high)
+    }
+}
+
+/// Splits the candidates at the golden ratio instead of the middle.
+pub struct GoldenSection;
+
+impl Strategy for GoldenSection {
+    fn name(&self) -> &str {
+        "golden"
+    }
+
+    fn next_guess(&mut self, low: u32, high: u32, _rng: &mut dyn RngCore) -> u32 {
+        const INVERSE_PHI_SQUARED: f64 = 0.381_966_011_250_105;
+
+        low + (f64::from(high - low) * INVERSE_PHI_SQUARED).round() as u32
+    }
+}
+
+/// Counts up from the bottom; the deliberately bad baseline.
+pub struct LinearScan;
+
+impl Strategy for LinearScan {
+    fn name(&self) -> &str {
+        "linear"
+    }
+
+    fn next_guess(&mut self, low: u32, _high: u32, _rng: &mut dyn RngCore) -> u32 {
+        low
+    }
+}
+
+pub fn all_strategies() -> Vec<Box<dyn Strategy>> {
+    vec![
+        Box::new(BinarySearch),
+        Box::new(GoldenSection),
+        Box::new(RandomGuess),
+        Box::new(LinearScan),
+    ]
+}
+
+/// Plays one game with `strategy` and returns the number of attempts.
+pub fn solve(strategy: &mut dyn Strategy, game: &mut Game, rng: &mut dyn RngCore) -> u32 {
+    let mut low = *game.range().start();
+    let mut high = *game.range().end();
+
+    while !game.is_over() {
+        let guess = strategy.next_guess(low, high, rng).clamp(low, high);
+
+        match game.guess(guess) {
+            Ordering::Less => low = guess + 1,
+            Ordering::Greater => high = guess - 1,
+            Ordering::Equal => {}
+        }
+    }
+
+    game.attempts()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub strategy: String,
+    pub games: usize,
+    pub mean: f64,
+    pub median: f64,
+    pub worst: u32,
+}
+
+/// Plays `games` seeded games with `strategy`.
+///
+/// Game `i` is seeded with `seed + i`, so every strategy faces the same
+/// sequence of secrets.
+pub fn simulate(
+    strategy: &mut dyn Strategy,
+    range: &RangeInclusive<u32>,
+    games: usize,
+    seed: u64,
+) -> Report {
+    let mut attempts: Vec<u32> = (0..games as u64)
+        .map(|i| {
+            let mut rng = StdRng::seed_from_u64(seed.wrapping_add(i));
+            let mut game = Game::with_rng(&mut rng, range.clone());
+
+            solve(strategy, &mut game, &mut rng)
+        })
+        .collect();
+    attempts.sort_unstable();
+
+    let total: u64 = attempts.iter().map(|&n| u64::from(n)).sum();
+    let mean = if games == 0 {
+        0.0
+    } else {
+        total as f64 / games as f64
+    };
+
+    let median = match games {
+        0 => 0.0,
+        n if n % 2 == 1 => f64::from(attempts[n / 2]),
+        n => (f64::from(attempts[n / 2 - 1]) + f64::from(attempts[n / 2])) / 2.0,
+    };
+
+    Report {
+        strategy: strategy.name().to_string(),
+        games,
+        mean,
+        median,
+        worst: attempts.last().copied().unwrap_or(0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reverse::worst_case_guesses;
+
+    const GAMES: usize = 500;
+
+    /// Plays every secret in `range` once, so the worst case is reached.
+    fn worst(strategy: &mut dyn Strategy, range: RangeInclusive<u32>) -> u32 {
+        let mut rng = StdRng::seed_from_u64(0);
+        range
+            .clone()
+            .map(|secret| {
+                let mut game = Game::new(secret).with_range(range.clone());
+                solve(strategy, &mut game, &mut rng)
+            })
+            .max()
+            .unwrap()
+    }
+
+    #[test]
+    fn binary_search_needs_at_most_log2_guesses() {
+        for range in [1..=1, 1..=2, 1..=100, 1..=1000, 0..=1023, 1..=1024] {
+            assert_eq!(
+                worst(&mut BinarySearch, range.clone()),
+                worst_case_guesses(&range),
+                "{range:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn golden_section_needs_at_most_log_phi_guesses() {
+        let phi = (1.0 + 5f64.sqrt()) / 2.0;
+
+        for range in [1..=10, 1..=100, 1..=1000] {
+            let size = f64::from(range.end() - range.start() + 1);
+            let bound = size.ln() / phi.ln() + 1.0;
+            assert!(
+                f64::from(worst(&mut GoldenSection, range.clone())) <= bound.ceil(),
+                "{range:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn every_strategy_solves_seeded_games() {
+        let range = 1..=100;
+
+        for mut strategy in all_strategies() {
+            let report = simulate(strategy.as_mut(), &range, GAMES, 7);
+
+            let bound = match report.strategy.as_str() {
+                "binary" => worst_case_guesses(&range),
+                "golden" => 10,
+                _ => 100,
+            };
+            assert_eq!(report.games, GAMES);
+            assert!(report.worst <= bound, "{report:?}");
+            assert!(report.mean >= 1.0 && report.mean <= f64::from(report.worst));
+            assert!(report.median <= f64::from(report.worst));
+        }
+    }
+
+    #[test]
+    fn the_same_seed_gives_the_same_report() {
+        for mut strategy in all_strategies() {
+            let first = simulate(strategy.as_mut(), &(1..=1000), GAMES, 42);
+            let second = simulate(strategy.as_mut(), &(1..=1000), GAMES, 42);
+            assert_eq!(first, second);
+        }
+
+        let one = simulate(&mut RandomGuess, &(1..=1000), GAMES, 1);
+        let other = simulate(&mut RandomGuess, &(1..=1000), GAMES, 2);
+        assert_ne!(one, other);
+    }
+
+    #[test]
+    fn no_games_make_an_empty_report() {
+        let report = simulate(&mut BinarySearch, &(1..=10), 0, 0);
+        assert_eq!((report.mean, report.median, report.worst), (0.0, 0.0, 0));
+    }
+}
@@ -0,0 +1,321 @@
+use crate::GameMode;
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::Duration;
+
+/// One finished game as stored in the scores file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub player: String,
+    pub mode: GameMode,
+    /// The number range; both zero for word games.
+    pub min: u32,
+    pub max: u32,
+    pub attempts: u32,
+    pub won: bool,
+    pub duration: Duration,
+    pub seed: u64,
+}
+
+impl Record {
+    /// Formats the record as one tab-separated line, without the newline.
+    pub fn to_line(&self) -> String {
+        let player: String = self
+            .player
+            .chars()
+            .map(|c| if c.is_control() { ' ' } else { c })
+            .collect();
+        let result = if self.won { "won" } else { "lost" };
+
+        format!(
+            "{player}\t{}\t{}\t{}\t{result}\t{}\t{}\t{}",
+            self.min,
+            self.max,
+            self.attempts,
+            self.duration.as_millis(),
+            self.seed,
+            self.mode.name()
+        )
+    }
+
+    /// Time-attack points: 1,000 for a win, less 50 for every attempt after
+    /// the first and 10 for every second taken. `None` for other modes.
+    pub fn score(&self) -> Option<u32> {
+        if self.mode != GameMode::TimeAttack {
+            return None;
+        }
+        if !self.won {
+            return Some(0);
+        }
+
+        let attempts = self.attempts.saturating_sub(1).saturating_mul(50);
+        let tenths = u32::try_from(self.duration.as_millis() / 100).unwrap_or(u32::MAX);
+        Some(1_000u32.saturating_sub(attempts).saturating_sub(tenths))
+    }
+
+    pub fn from_line(line: &str) -> Option<Record> {
+        let mut fields = line.split('\t');
+
+        let player = fields.next()?.to_string();
+        let min = fields.next()?.parse().ok()?;
+        let max = fields.next()?.parse().ok()?;
+        let attempts = fields.next()?.parse().ok()?;
+        let won = match fields.next()? {
+            "won" => true,
+            "lost" => false,
+            _ => return None,
+        };
+        let duration = Duration::from_millis(fields.next()?.parse().ok()?);
+        let seed = fields.next()?.parse().ok()?;
+        // Files written before word mode existed have no mode column.
+        let mode = match fields.next() {
+            Some(name) => GameMode::parse(name)?,
+            None => GameMode::Number,
+        };
+
+        if fields.next().is_some() {
+            return None;
+        }
+
+        Some(Record {
+            player,
+            mode,
+            min,
+            max,
+            attempts,
+            won,
+            duration,
+            seed,
+        })
+    }
+}
+
+/// Reads every record from `path`; a missing file means no games yet.
+pub fn load(path: &Path) -> io::Result<Vec<Record>> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+
+    let mut records = Vec::new();
+    for (number, line) in contents.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let record = Record::from_line(line).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}:{}: malformed record", path.display(), number + 1),
+            )
+        })?;
+        records.push(record);
+    }
+
+    Ok(records)
+}
+
+pub fn append(path: &Path, record: &Record) -> io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+
+    writeln!(file, "{}", record.to_line())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub games: u32,
+    pub wins: u32,
+    pub best: Option<u32>,
+    /// The mean attempts of the games won. Lost games are left out, as
+    /// they only say how many attempts the player was allowed.
+    pub average_attempts: Option<f64>,
+    pub current_streak: u32,
+    pub longest_streak: u32,
+    /// Number of games that took each attempt count.
+    pub histogram: BTreeMap<u32, u32>,
+}
+
+impl Stats {
+    /// Summarizes `records`, which must be in the order they were played.
+    pub fn from_records<'a>(records: impl IntoIterator<Item = &'a Record>) -> Stats {
+        let mut stats = Stats {
+            games: 0,
+            wins: 0,
+            best: None,
+            average_attempts: None,
+            current_streak: 0,
+            longest_streak: 0,
+            histogram: BTreeMap::new(),
+        };
+        let mut winning_attempts: u64 = 0;
+
+        for record in records {
+            stats.games += 1;
+            *stats.histogram.entry(record.attempts).or_insert(0) += 1;
+
+            if record.won {
+                stats.wins += 1;
+                winning_attempts += u64::from(record.attempts);
+                stats.current_streak += 1;
+                stats.longest_streak = stats.longest_streak.max(stats.current_streak);
+                stats.best = Some(match stats.best {
+                    Some(best) => best.min(record.attempts),
+                    None => record.attempts,
+                });
+            } else {
+                stats.current_streak = 0;
+            }
+        }
+
+        if stats.wins > 0 {
+            stats.average_attempts = Some(winning_attempts as f64 / f64::from(stats.wins));
+        }
+
+        stats
+    }
+}
+
+/// The best wins for each range, keyed by `(min, max)`: highest
+/// time-attack score first, then fewest attempts, with faster games
+/// breaking ties.
+///
+/// Attempts are only comparable within one range, as a wider one takes
+/// more guesses, so every range gets its own board of up to `limit` wins.
+pub fn leaderboard(records: &[Record], limit: usize) -> BTreeMap<(u32, u32), Vec<&Record>> {
+    let mut boards: BTreeMap<(u32, u32), Vec<&Record>> = BTreeMap::new();
+    for record in records.iter().filter(|record| record.won) {
+        boards
+            .entry((record.min, record.max))
+            .or_default()
+            .push(record);
+    }
+
+    for wins in boards.values_mut() {
+        wins.sort_by_key(|record| (Reverse(record.score()), record.attempts, record.duration));
+        wins.truncate(limit);
+    }
+
+    boards
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn win(player: &str, max: u32, attempts: u32) -> Record {
+        Record {
+            player: player.to_string(),
+            mode: GameMode::Number,
+            min: 1,
+            max,
+            attempts,
+            won: true,
+            duration: Duration::from_secs(u64::from(attempts)),
+            seed: 0,
+        }
+    }
+
+    #[test]
+    fn leaderboard_ranks_each_range_separately() {
+        let lost = Record {
+            won: false,
+            ..win("dan", 10, 1)
+        };
+        let records = [
+            win("ann", 1_000_000, 12),
+            win("bob", 10, 3),
+            win("cat", 10, 2),
+            lost,
+            win("eve", 1_000_000, 20),
+        ];
+
+        let boards = leaderboard(&records, 10);
+        let players = |range| -> Vec<&str> {
+            boards[&range]
+                .iter()
+                .map(|record| record.player.as_str())
+                .collect()
+        };
+
+        assert_eq!(boards.len(), 2);
+        assert_eq!(players((1, 10)), ["cat", "bob"]);
+        assert_eq!(players((1, 1_000_000)), ["ann", "eve"]);
+    }
+
+    #[test]
+    fn leaderboard_keeps_the_best_of_each_range() {
+        let records = [win("ann", 10, 4), win("bob", 10, 1), win("cat", 10, 2)];
+
+        let boards = leaderboard(&records, 2);
+
+        assert_eq!(boards[&(1, 10)], [&records[1], &records[2]]);
+    }
+
+    #[test]
+    fn summarizes_wins_and_losses() {
+        let lost = |attempts| Record {
+            won: false,
+            ..win("ann", 100, attempts)
+        };
+        let records = [
+            win("ann", 100, 4),
+            win("ann", 100, 6),
+            lost(10),
+            win("ann", 100, 2),
+            win("ann", 100, 6),
+            win("ann", 100, 7),
+            lost(10),
+            win("ann", 100, 5),
+        ];
+
+        let stats = Stats::from_records(&records);
+
+        assert_eq!((stats.games, stats.wins), (8, 6));
+        assert_eq!(stats.best, Some(2));
+        // (4 + 6 + 2 + 6 + 7 + 5) / 6, without the two losses.
+        assert_eq!(stats.average_attempts, Some(5.0));
+        assert_eq!((stats.current_streak, stats.longest_streak), (1, 3));
+        assert_eq!(
+            stats.histogram,
+            BTreeMap::from([(2, 1), (4, 1), (5, 1), (6, 2), (7, 1), (10, 2)])
+        );
+    }
+
+    #[test]
+    fn summarizes_nothing_and_only_losses() {
+        let empty = Stats::from_records(&[]);
+        assert_eq!(
+            (empty.games, empty.best, empty.average_attempts),
+            (0, None, None)
+        );
+        assert!(empty.histogram.is_empty());
+
+        let lost = Record {
+            won: false,
+            ..win("ann", 10, 3)
+        };
+        let stats = Stats::from_records(&[lost.clone(), lost]);
+        assert_eq!((stats.games, stats.wins), (2, 0));
+        assert_eq!((stats.best, stats.average_attempts), (None, None));
+        assert_eq!((stats.current_streak, stats.longest_streak), (0, 0));
+        assert_eq!(stats.histogram, BTreeMap::from([(3, 2)]));
+    }
+
+    #[test]
+    fn records_survive_a_round_trip() {
+        let record = Record {
+            mode: GameMode::TimeAttack,
+            ..win("ann\tlee", 100, 7)
+        };
+        let parsed = Record::from_line(&record.to_line()).unwrap();
+
+        assert_eq!(parsed.player, "ann lee");
+        assert_eq!(parsed.mode, GameMode::TimeAttack);
+        assert_eq!((parsed.min, parsed.max, parsed.attempts), (1, 100, 7));
+        assert_eq!(parsed.score(), Some(1_000 - 6 * 50 - 70));
+    }
+}
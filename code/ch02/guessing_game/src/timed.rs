@@ -0,0 +1,349 @@
+use crate::{take_turn, Game, Outcome, Reporter, Secret};
+use std::io::{self, BufRead, BufReader};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// What waiting for a line of input can end with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input {
+    Line(String),
+    TimedOut,
+    /// The input is exhausted and the game stops.
+    Closed,
+}
+
+/// Like `GuessSource`, but the game can give up waiting.
+pub trait TimedSource {
+    fn next_line(&mut self, timeout: Duration) -> Input;
+}
+
+/// Lines handed over by another thread, so reading never blocks the game.
+pub struct ChannelSource {
+    lines: Receiver<String>,
+}
+
+impl ChannelSource {
+    pub fn new(lines: Receiver<String>) -> ChannelSource {
+        ChannelSource { lines }
+    }
+
+    /// Starts a thread that forwards every line of `reader`.
+    ///
+    /// The thread may stay blocked in `read_line` once the game is over; it
+    /// goes away with the process.
+    pub fn spawn<R: BufRead + Send + 'static>(reader: R) -> ChannelSource {
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            for line in reader.lines() {
+                let line = match line {
+                    Ok(line) => line,
+                    Err(_) => break,
+                };
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        ChannelSource::new(receiver)
+    }
+
+    pub fn stdin() -> ChannelSource {
+        ChannelSource::spawn(BufReader::new(io::stdin()))
+    }
+}
+
+impl TimedSource for ChannelSource {
+    fn next_line(&mut self, timeout: Duration) -> Input {
+        match self.lines.recv_timeout(timeout) {
+            Ok(line) => Input::Line(line),
+            Err(RecvTimeoutError::Timeout) => Input::TimedOut,
+            Err(RecvTimeoutError::Disconnected) => Input::Closed,
+        }
+    }
+}
+
+/// How soon after a timeout a line is still taken to be the late answer
+/// to the prompt that timed out: long enough to press Enter on a guess
+/// already typed, too short to read the new prompt and type another.
+pub const LATE_LINE: Duration = Duration::from_millis(1_500);
+
+/// The clock for a time-attack game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeLimits {
+    /// The game is lost once this much time has passed.
+    pub total: Duration,
+    /// A guess that takes longer counts as a miss.
+    pub per_guess: Duration,
+    /// The first line arriving within this long of a timeout is dropped,
+    /// as it was typed for the guess that already counted as a miss.
+    pub late: Duration,
+}
+
+/// Like `play`, but against the clock.
+///
+/// Each guess waits at most `per_guess`, or whatever is left of `total` if
+/// that is less. A slow guess costs an attempt; running out of total time
+/// loses the game. The player may still be typing when a guess times out,
+/// so a line that arrives within `late` of that is dropped instead of being
+/// scored against the next prompt.
+pub fn play_timed<S, G, P>(
+    game: &mut Game<S>,
+    source: &mut G,
+    reporter: &mut P,
+    limits: &TimeLimits,
+) -> Outcome<S::Answer>
+where
+    S: Secret,
+    G: TimedSource,
+    P: Reporter<S>,
+{
+    let started = Instant::now();
+    let mut timed_out_at: Option<Instant> = None;
+    reporter.start();
+
+    loop {
+        let left = limits.total.saturating_sub(started.elapsed());
+        if left.is_zero() {
+            let outcome = game.lose();
+            reporter.finish(&outcome);
+            return outcome;
+        }
+
+        reporter.time_left(left);
+        reporter.prompt();
+
+        let after_timeout = timed_out_at.take();
+        match source.next_line(left.min(limits.per_guess)) {
+            Input::Line(line) if after_timeout.is_some_and(|at| at.elapsed() < limits.late) => {
+                reporter.late(&line);
+            }
+            Input::Line(line) => take_turn(game, reporter, &line),
+            // Out of total time: the check above ends the game.
+            Input::TimedOut if started.elapsed() >= limits.total => continue,
+            Input::TimedOut => {
+                game.miss();
+                timed_out_at = Some(Instant::now());
+                reporter.timed_out();
+                reporter.attempts(game.attempts());
+            }
+            Input::Closed => {
+                let outcome = game.quit();
+                reporter.finish(&outcome);
+                return outcome;
+            }
+        }
+
+        if let Some(outcome) = game.outcome() {
+            reporter.finish(&outcome);
+            return outcome;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GuessError, Number};
+    use std::cmp::Ordering;
+    use std::collections::VecDeque;
+
+    /// Hands out each input after its delay, then reports the input closed,
+    /// and remembers how long it was asked to wait each time.
+    struct Fake {
+        inputs: VecDeque<(Duration, Input)>,
+        waits: Vec<Duration>,
+    }
+
+    impl Fake {
+        fn new(inputs: Vec<(u64, Input)>) -> Fake {
+            let inputs = inputs
+                .into_iter()
+                .map(|(millis, input)| (Duration::from_millis(millis), input))
+                .collect();
+            Fake {
+                inputs,
+                waits: Vec::new(),
+            }
+        }
+    }
+
+    impl TimedSource for Fake {
+        fn next_line(&mut self, timeout: Duration) -> Input {
+            self.waits.push(timeout);
+            let (delay, input) = self
+                .inputs
+                .pop_front()
+                .unwrap_or((Duration::ZERO, Input::Closed));
+            thread::sleep(delay);
+            input
+        }
+    }
+
+    #[derive(Default)]
+    struct Log(Vec<String>);
+
+    impl Reporter<Number> for Log {
+        fn start(&mut self) {}
+
+        fn prompt(&mut self) {}
+
+        fn invalid(&mut self, error: &GuessError) {
+            self.0.push(format!("invalid {error}"));
+        }
+
+        fn guessed(&mut self, guess: u32) {
+            self.0.push(format!("guessed {guess}"));
+        }
+
+        fn feedback(&mut self, _ordering: Ordering) {}
+
+        fn timed_out(&mut self) {
+            self.0.push("timed out".into());
+        }
+
+        fn late(&mut self, line: &str) {
+            self.0.push(format!("late {line}"));
+        }
+
+        fn finish(&mut self, outcome: &Outcome) {
+            self.0.push(format!("{outcome:?}"));
+        }
+    }
+
+    fn line(text: &str) -> Input {
+        Input::Line(text.to_string())
+    }
+
+    fn limits(total: u64, per_guess: u64, late: u64) -> TimeLimits {
+        TimeLimits {
+            total: Duration::from_millis(total),
+            per_guess: Duration::from_millis(per_guess),
+            late: Duration::from_millis(late),
+        }
+    }
+
+    fn play(game: &mut Game, source: &mut Fake, limits: &TimeLimits) -> (Outcome, Vec<String>) {
+        let mut log = Log::default();
+        let outcome = play_timed(game, source, &mut log, limits);
+        (outcome, log.0)
+    }
+
+    #[test]
+    fn a_slow_guess_costs_an_attempt() {
+        let mut game = Game::new(7);
+        let mut source = Fake::new(vec![(0, Input::TimedOut), (0, line("7"))]);
+
+        let (outcome, log) = play(&mut game, &mut source, &limits(60_000, 5_000, 0));
+
+        assert_eq!(outcome, Outcome::Won { attempts: 2 });
+        assert_eq!(log, ["timed out", "guessed 7", "Won { attempts: 2 }"]);
+        assert_eq!(source.waits[0], Duration::from_secs(5));
+    }
+
+    #[test]
+    fn timeouts_can_use_up_every_attempt() {
+        let mut game = Game::new(7).with_max_attempts(2);
+        let mut source = Fake::new(vec![(0, Input::TimedOut), (0, Input::TimedOut)]);
+
+        let (outcome, _) = play(&mut game, &mut source, &limits(60_000, 5_000, 0));
+
+        assert_eq!(
+            outcome,
+            Outcome::Lost {
+                attempts: 2,
+                secret: 7
+            }
+        );
+    }
+
+    #[test]
+    fn drops_the_line_finished_just_after_a_timeout() {
+        let mut game = Game::new(7);
+        let mut source = Fake::new(vec![(0, Input::TimedOut), (0, line("3")), (0, line("7"))]);
+
+        let (outcome, log) = play(&mut game, &mut source, &limits(60_000, 5_000, 60_000));
+
+        assert_eq!(outcome, Outcome::Won { attempts: 2 });
+        assert_eq!(
+            log,
+            ["timed out", "late 3", "guessed 7", "Won { attempts: 2 }"]
+        );
+    }
+
+    #[test]
+    fn only_drops_one_line_and_only_soon_after() {
+        let mut game = Game::new(7);
+        let mut source = Fake::new(vec![
+            (0, Input::TimedOut),
+            (50, line("3")),
+            (0, Input::TimedOut),
+            (0, line("4")),
+            (0, line("7")),
+        ]);
+
+        let (outcome, log) = play(&mut game, &mut source, &limits(60_000, 5_000, 20));
+
+        assert_eq!(outcome, Outcome::Won { attempts: 4 });
+        assert_eq!(
+            log,
+            [
+                "timed out",
+                "guessed 3",
+                "timed out",
+                "late 4",
+                "guessed 7",
+                "Won { attempts: 4 }"
+            ]
+        );
+    }
+
+    #[test]
+    fn running_out_of_time_loses_the_game() {
+        let mut game = Game::new(7);
+        let mut source = Fake::new(vec![(0, line("1")), (40, Input::TimedOut)]);
+
+        let (outcome, log) = play(&mut game, &mut source, &limits(20, 5_000, 0));
+
+        assert_eq!(
+            outcome,
+            Outcome::Lost {
+                attempts: 1,
+                secret: 7
+            }
+        );
+        // Running out of total time is not a slow guess.
+        assert!(!log.contains(&"timed out".to_string()));
+        assert!(source
+            .waits
+            .iter()
+            .all(|&wait| wait <= Duration::from_millis(20)));
+    }
+
+    #[test]
+    fn closed_input_quits() {
+        let mut game = Game::new(7);
+        let mut source = Fake::new(vec![(0, line("1"))]);
+
+        let (outcome, log) = play(&mut game, &mut source, &limits(60_000, 5_000, 0));
+
+        assert_eq!(outcome, Outcome::Quit { attempts: 1 });
+        assert_eq!(log.last().unwrap(), "Quit { attempts: 1 }");
+    }
+
+    #[test]
+    fn channel_source_reads_lines_then_closes() {
+        let mut source = ChannelSource::spawn("12\nabc\n".as_bytes());
+        let wait = Duration::from_secs(5);
+
+        assert_eq!(source.next_line(wait), line("12"));
+        assert_eq!(source.next_line(wait), line("abc"));
+        assert_eq!(source.next_line(wait), Input::Closed);
+
+        let (_sender, receiver) = mpsc::channel();
+        let mut idle = ChannelSource::new(receiver);
+        assert_eq!(idle.next_line(Duration::from_millis(1)), Input::TimedOut);
+    }
+}
@@ -0,0 +1,308 @@
+use crate::{GuessError, Hint, Lang, Outcome, Reporter};
+use std::cmp::Ordering;
+use std::io::Write;
+use std::ops::RangeInclusive;
+use std::sync::{Arc, Mutex, MutexGuard, Weak};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const BAR_WIDTH: u64 = 50;
+const HISTORY_LINES: usize = 10;
+/// How often the clock in the title line is redrawn.
+const TICK: Duration = Duration::from_secs(1);
+
+const CLEAR: &str = "\x1b[2J\x1b[H";
+/// Redraws the title line without moving the cursor away from the prompt.
+const SAVE_CURSOR: &str = "\x1b7";
+const TOP_LINE: &str = "\x1b[H\x1b[2K";
+const RESTORE_CURSOR: &str = "\x1b8";
+const BOLD: &str = "\x1b[1m";
+const GREEN: &str = "\x1b[32m";
+const RED: &str = "\x1b[31m";
+const BLUE: &str = "\x1b[34m";
+const RESET: &str = "\x1b[0m";
+
+/// Redraws the whole screen on every turn instead of scrolling.
+///
+/// Uses plain ANSI escapes, so it works in any terminal that understands
+/// them; the line-based reporter remains the default for pipes. A
+/// background thread keeps the clock running while the player types.
+pub struct Tui<W: Write> {
+    screen: Arc<Mutex<Screen<W>>>,
+}
+
+struct Screen<W: Write> {
+    out: W,
+    lang: Lang,
+    range: RangeInclusive<u32>,
+    low: u32,
+    high: u32,
+    max_attempts: Option<u32>,
+    attempts: u32,
+    history: Vec<(u32, Ordering)>,
+    guess: Option<u32>,
+    message: String,
+    started: Instant,
+    finished: Option<Outcome>,
+}
+
+impl<W: Write + Send + 'static> Tui<W> {
+    pub fn new(
+        out: W,
+        lang: Lang,
+        range: RangeInclusive<u32>,
+        max_attempts: Option<u32>,
+    ) -> Tui<W> {
+        let screen = Arc::new(Mutex::new(Screen {
+            out,
+            lang,
+            low: *range.start(),
+            high: *range.end(),
+            range,
+            max_attempts,
+            attempts: 0,
+            history: Vec::new(),
+            guess: None,
+            message: String::new(),
+            started: Instant::now(),
+            finished: None,
+        }));
+
+        let ticking = Arc::downgrade(&screen);
+        thread::spawn(move || tick(ticking));
+
+        Tui { screen }
+    }
+
+    fn screen(&self) -> MutexGuard<'_, Screen<W>> {
+        self.screen.lock().unwrap()
+    }
+}
+
+/// Redraws the title line every `TICK` until the game ends or the
+/// `Tui` is dropped.
+fn tick<W: Write>(screen: Weak<Mutex<Screen<W>>>) {
+    loop {
+        thread::sleep(TICK);
+
+        let Some(screen) = screen.upgrade() else {
+            return;
+        };
+        let mut screen = screen.lock().unwrap();
+        if screen.finished.is_some() {
+            return;
+        }
+
+        let title = screen.title();
+        let _ = write!(screen.out, "{SAVE_CURSOR}{TOP_LINE}{title}{RESTORE_CURSOR}");
+        let _ = screen.out.flush();
+    }
+}
+
+impl<W: Write> Screen<W> {
+    /// One cell per slice of the range, filled while it can still hold the secret.
+    fn bar(&self) -> String {
+        let start = u64::from(*self.range.start());
+        let size = u64::from(*self.range.end()) - start + 1;
+        let cells = BAR_WIDTH.min(size);
+
+        (0..cells)
+            .map(|cell| {
+                let first = start + cell * size / cells;
+                let last = start + (cell + 1) * size / cells - 1;
+                if last >= u64::from(self.low) && first <= u64::from(self.high) {
+                    '█'
+                } else {
+                    '·'
+                }
+            })
+            .collect()
+    }
+
+    /// The title, the attempts used and the time taken, on one line.
+    fn title(&self) -> String {
+        let elapsed = self.started.elapsed().as_secs();
+        let attempts = match self.max_attempts {
+            Some(max) => format!("{}/{max}", self.attempts),
+            None => self.attempts.to_string(),
+        };
+
+        let lang = self.lang;
+        format!(
+            "{BOLD}{}{RESET}    {}: {attempts}    {}: {:02}:{:02}",
+            lang.title(),
+            lang.attempts_label(),
+            lang.time_label(),
+            elapsed / 60,
+            elapsed % 60
+        )
+    }
+
+    fn render(&mut self) {
+        let lang = self.lang;
+        let mut screen = String::from(CLEAR);
+        screen += &self.title();
+        screen += "\n\n";
+        screen += &format!(
+            "  {} {BLUE}{}{RESET} {}\n",
+            self.range.start(),
+            self.bar(),
+            self.range.end()
+        );
+        if self.finished.is_none() {
+            screen += &format!("  {}\n", lang.candidates(self.low, self.high));
+        }
+
+        screen += &format!("\n{}:\n", lang.history_label());
+        let skipped = self.history.len().saturating_sub(HISTORY_LINES);
+        if skipped > 0 {
+            screen += &format!("  {}\n", lang.earlier(skipped));
+        }
+        for (guess, ordering) in &self.history[skipped..] {
+            let marker = match ordering {
+                Ordering::Less => format!("{RED}▲ {}{RESET}", lang.too_small()),
+                Ordering::Greater => format!("{RED}▼ {}{RESET}", lang.too_big()),
+                Ordering::Equal => format!("{GREEN}★ {}{RESET}", lang.correct()),
+            };
+            screen += &format!("  {guess:>10}  {marker}\n");
+        }
+
+        screen += "\n";
+        screen += &self.message;
+        screen += "\n";
+
+        let _ = write!(self.out, "{screen}");
+        let _ = self.out.flush();
+    }
+}
+
+impl<W: Write + Send + 'static> Reporter for Tui<W> {
+    fn start(&mut self) {
+        let mut screen = self.screen();
+        screen.started = Instant::now();
+        screen.render();
+    }
+
+    fn prompt(&mut self) {
+        let mut screen = self.screen();
+        screen.render();
+        let _ = write!(screen.out, "> ");
+        let _ = screen.out.flush();
+        screen.message.clear();
+    }
+
+    fn invalid(&mut self, error: &GuessError) {
+        let mut screen = self.screen();
+        screen.message = screen.lang.guess_error(error);
+    }
+
+    fn guessed(&mut self, guess: u32) {
+        self.screen().guess = Some(guess);
+    }
+
+    fn feedback(&mut self, ordering: Ordering) {
+        let mut screen = self.screen();
+        let guess = match screen.guess.take() {
+            Some(guess) => guess,
+            None => return,
+        };
+
+        match ordering {
+            Ordering::Less => screen.low = screen.low.max(guess + 1),
+            Ordering::Greater => screen.high = screen.high.min(guess - 1),
+            Ordering::Equal => {
+                screen.low = guess;
+                screen.high = guess;
+            }
+        }
+        screen.history.push((guess, ordering));
+    }
+
+    fn attempts(&mut self, used: u32) {
+        self.screen().attempts = used;
+    }
+
+    fn hint(&mut self, hint: &Hint) {
+        let mut screen = self.screen();
+        screen.message = screen.lang.hint(hint);
+    }
+
+    fn finish(&mut self, outcome: &Outcome) {
+        let mut screen = self.screen();
+        screen.finished = Some(*outcome);
+        screen.attempts = outcome.attempts();
+        screen.message = match outcome {
+            Outcome::Won { attempts } => format!("{GREEN}{}{RESET}", screen.lang.win(*attempts)),
+            Outcome::Lost { secret, .. } => format!("{RED}{}{RESET}", screen.lang.lose(*secret)),
+            Outcome::Quit { .. } => screen.lang.bye().to_string(),
+        };
+        screen.render();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{play, Game, GuessSource};
+
+    /// An output the test can still read after handing it to the `Tui`.
+    #[derive(Clone, Default)]
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(bytes)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Shared {
+        /// Everything written since the screen was last cleared.
+        fn last_screen(&self) -> String {
+            let text = String::from_utf8(self.0.lock().unwrap().clone()).unwrap();
+            text.rsplit(CLEAR).next().unwrap().to_string()
+        }
+    }
+
+    struct Lines(Vec<&'static str>);
+
+    impl GuessSource for Lines {
+        fn next_guess(&mut self) -> Option<String> {
+            (!self.0.is_empty()).then(|| self.0.remove(0).to_string())
+        }
+    }
+
+    #[test]
+    fn counts_rejected_input_in_strict_mode() {
+        let out = Shared::default();
+        let mut tui = Tui::new(out.clone(), Lang::En, 1..=10, Some(5));
+        let mut game = Game::new(7)
+            .with_range(1..=10)
+            .with_strict(true)
+            .with_max_attempts(5);
+
+        play(&mut game, &mut Lines(vec!["abc", "3", "11"]), &mut tui);
+
+        let screen = out.last_screen();
+        assert!(screen.contains("Attempts: 3/5"), "{screen}");
+        assert_eq!(screen.matches('▲').count(), 1);
+    }
+
+    #[test]
+    fn ticks_until_the_game_ends() {
+        let out = Shared::default();
+        let mut tui = Tui::new(out.clone(), Lang::En, 1..=10, None);
+        Reporter::start(&mut tui);
+
+        thread::sleep(TICK + TICK / 2);
+        assert!(out.last_screen().contains(SAVE_CURSOR));
+
+        play(&mut Game::new(7), &mut Lines(vec!["7"]), &mut tui);
+        let finished = out.0.lock().unwrap().len();
+        thread::sleep(TICK + TICK / 2);
+        assert_eq!(out.0.lock().unwrap().len(), finished);
+    }
+}
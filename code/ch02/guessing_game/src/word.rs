@@ -0,0 +1,356 @@
+use crate::secret::Secret;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+pub const WORD_LENGTH: usize = 5;
+
+/// The bundled list of five-letter words, one per line.
+const WORDS: &str = include_str!("words.txt");
+
+pub fn word_list() -> Vec<&'static str> {
+    WORDS.lines().filter(|word| !word.is_empty()).collect()
+}
+
+/// Feedback for one letter of a guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Letter {
+    /// Right letter in the right place.
+    Correct,
+    /// In the word, but somewhere else.
+    Present,
+    Absent,
+}
+
+pub type Score = [Letter; WORD_LENGTH];
+
+/// Scores `guess` against `answer`, both `WORD_LENGTH` ASCII letters.
+///
+/// Exact matches are taken first, so a repeated letter is only marked
+/// present as many times as it is still unaccounted for in the answer.
+pub fn score(answer: &str, guess: &str) -> Score {
+    let answer = answer.as_bytes();
+    let guess = guess.as_bytes();
+    let mut score = [Letter::Absent; WORD_LENGTH];
+    let mut unmatched: HashMap<u8, usize> = HashMap::new();
+
+    for i in 0..WORD_LENGTH {
+        if guess[i] == answer[i] {
+            score[i] = Letter::Correct;
+        } else {
+            *unmatched.entry(answer[i]).or_insert(0) += 1;
+        }
+    }
+
+    for i in 0..WORD_LENGTH {
+        if score[i] == Letter::Correct {
+            continue;
+        }
+        if let Some(count) = unmatched.get_mut(&guess[i]) {
+            if *count > 0 {
+                *count -= 1;
+                score[i] = Letter::Present;
+            }
+        }
+    }
+
+    score
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WordError {
+    WrongLength(usize),
+    NotALetter(char),
+    NotInList(String),
+    /// Hard mode: a revealed green letter was moved.
+    MustPlace {
+        letter: char,
+        position: usize,
+    },
+    /// Hard mode: a revealed letter was left out.
+    MustUse(char),
+}
+
+impl fmt::Display for WordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WordError::WrongLength(len) => {
+                write!(f, "Guesses have {WORD_LENGTH} letters, not {len}.")
+            }
+            WordError::NotALetter(c) => write!(f, "{c:?} is not a letter."),
+            WordError::NotInList(word) => write!(f, "{word:?} is not in the word list."),
+            WordError::MustPlace { letter, position } => write!(
+                f,
+                "Hard mode: letter {} must be {}.",
+                position + 1,
+                letter.to_ascii_uppercase()
+            ),
+            WordError::MustUse(letter) => write!(
+                f,
+                "Hard mode: the guess must contain {}.",
+                letter.to_ascii_uppercase()
+            ),
+        }
+    }
+}
+
+impl Error for WordError {}
+
+/// A five-letter word with per-letter feedback.
+#[derive(Debug, Clone)]
+pub struct Word {
+    answer: String,
+    words: Vec<&'static str>,
+    hard: bool,
+    /// Letters revealed in their place so far.
+    placed: [Option<u8>; WORD_LENGTH],
+    /// How many of each letter every later guess must contain.
+    required: HashMap<u8, usize>,
+}
+
+impl Word {
+    /// `answer` is lowercased; it does not have to be in the word list.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless `answer` is exactly `WORD_LENGTH` ASCII letters.
+    pub fn new(answer: &str, hard: bool) -> Word {
+        assert!(
+            answer.len() == WORD_LENGTH && answer.bytes().all(|b| b.is_ascii_alphabetic()),
+            "the answer must be {WORD_LENGTH} ASCII letters, got {answer:?}"
+        );
+
+        Word {
+            answer: answer.to_lowercase(),
+            words: word_list(),
+            hard,
+            placed: [None; WORD_LENGTH],
+            required: HashMap::new(),
+        }
+    }
+
+    pub fn random<R: Rng>(rng: &mut R, hard: bool) -> Word {
+        let words = word_list();
+        let answer = words.choose(rng).expect("the word list is not empty");
+
+        Word::new(answer, hard)
+    }
+
+    pub fn is_hard(&self) -> bool {
+        self.hard
+    }
+
+    fn check_hard_mode(&self, guess: &str) -> Result<(), WordError> {
+        let bytes = guess.as_bytes();
+
+        for (position, letter) in self.placed.iter().enumerate() {
+            if let Some(letter) = *letter {
+                if bytes[position] != letter {
+                    return Err(WordError::MustPlace {
+                        letter: char::from(letter),
+                        position,
+                    });
+                }
+            }
+        }
+
+        for (&letter, &count) in &self.required {
+            if bytes.iter().filter(|&&b| b == letter).count() < count {
+                return Err(WordError::MustUse(char::from(letter)));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn remember(&mut self, guess: &str, score: &Score) {
+        let mut found: HashMap<u8, usize> = HashMap::new();
+
+        for (i, (&letter, result)) in guess.as_bytes().iter().zip(score).enumerate() {
+            match result {
+                Letter::Correct => {
+                    self.placed[i] = Some(letter);
+                    *found.entry(letter).or_insert(0) += 1;
+                }
+                Letter::Present => *found.entry(letter).or_insert(0) += 1,
+                Letter::Absent => {}
+            }
+        }
+
+        for (letter, count) in found {
+            let required = self.required.entry(letter).or_insert(0);
+            *required = (*required).max(count);
+        }
+    }
+}
+
+impl Secret for Word {
+    type Guess = String;
+    type Feedback = Score;
+    type Error = WordError;
+    type Answer = String;
+
+    fn parse(&self, input: &str) -> Result<String, WordError> {
+        let guess = input.trim().to_lowercase();
+
+        if let Some(c) = guess.chars().find(|c| !c.is_ascii_alphabetic()) {
+            return Err(WordError::NotALetter(c));
+        }
+        if guess.len() != WORD_LENGTH {
+            return Err(WordError::WrongLength(guess.len()));
+        }
+        if guess != self.answer && !self.words.contains(&guess.as_str()) {
+            return Err(WordError::NotInList(guess));
+        }
+
+        Ok(guess)
+    }
+
+    fn check(&mut self, guess: &String) -> Result<Score, WordError> {
+        if self.hard {
+            self.check_hard_mode(guess)?;
+        }
+
+        let score = score(&self.answer, guess);
+        self.remember(guess, &score);
+
+        Ok(score)
+    }
+
+    fn is_solved(&self, score: &Score) -> bool {
+        score.iter().all(|&letter| letter == Letter::Correct)
+    }
+
+    fn answer(&self) -> String {
+        self.answer.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A score written as `g` for correct, `y` for present and `-` for
+    /// absent, the way the colours of the tiles read.
+    fn tiles(pattern: &str) -> Score {
+        let mut score = [Letter::Absent; WORD_LENGTH];
+        for (letter, tile) in score.iter_mut().zip(pattern.chars()) {
+            *letter = match tile {
+                'g' => Letter::Correct,
+                'y' => Letter::Present,
+                _ => Letter::Absent,
+            };
+        }
+        score
+    }
+
+    #[test]
+    fn scores_guesses() {
+        let cases = [
+            ("crane", "crane", "ggggg"),
+            ("crane", "zzzzz", "-----"),
+            ("crane", "nacre", "yyyyg"),
+            // A guess with two of a letter the answer has once: only the
+            // first is present.
+            ("plant", "array", "y----"),
+            // An exact match takes the letter even from an earlier copy.
+            ("plant", "alarm", "-gg--"),
+            // The answer has two, the guess three: the third is absent.
+            ("sweet", "eerie", "yy---"),
+            ("abbey", "kebab", "-ygyy"),
+            ("abbey", "babes", "yygg-"),
+        ];
+
+        for (answer, guess, expected) in cases {
+            assert_eq!(
+                score(answer, guess),
+                tiles(expected),
+                "{guess} for {answer}"
+            );
+        }
+    }
+
+    #[test]
+    fn parses_guesses() {
+        let word = Word::new("CRANE", false);
+
+        assert_eq!(word.parse(" Crane\n"), Ok(String::from("crane")));
+        assert_eq!(word.parse("cran"), Err(WordError::WrongLength(4)));
+        assert_eq!(word.parse("cr4ne"), Err(WordError::NotALetter('4')));
+        assert_eq!(
+            word.parse("zzzzz"),
+            Err(WordError::NotInList(String::from("zzzzz")))
+        );
+        assert_eq!(word.answer(), "crane");
+    }
+
+    #[test]
+    fn easy_mode_accepts_any_guess() {
+        let mut word = Word::new("crane", false);
+
+        word.check(&String::from("chose")).unwrap();
+        assert!(word.check(&String::from("zzzzz")).is_ok());
+    }
+
+    #[test]
+    fn hard_mode_keeps_green_letters_in_place() {
+        let mut word = Word::new("crane", true);
+        assert_eq!(word.check(&String::from("cloud")), Ok(tiles("g----")));
+
+        let err = word.check(&String::from("bloat")).unwrap_err();
+        assert_eq!(
+            err,
+            WordError::MustPlace {
+                letter: 'c',
+                position: 0
+            }
+        );
+        assert_eq!(err.to_string(), "Hard mode: letter 1 must be C.");
+        assert!(word.check(&String::from("chimp")).is_ok());
+    }
+
+    #[test]
+    fn hard_mode_keeps_yellow_letters_in_the_guess() {
+        let mut word = Word::new("crane", true);
+        word.check(&String::from("punky")).unwrap();
+
+        let err = word.check(&String::from("bloat")).unwrap_err();
+        assert_eq!(err, WordError::MustUse('n'));
+        assert_eq!(err.to_string(), "Hard mode: the guess must contain N.");
+        // Anywhere will do, even the place it was yellow in.
+        assert!(word.check(&String::from("nymph")).is_ok());
+        assert!(word.check(&String::from("funky")).is_ok());
+    }
+
+    #[test]
+    fn hard_mode_counts_repeated_letters() {
+        let mut word = Word::new("abbey", true);
+        assert_eq!(word.check(&String::from("babes")), Ok(tiles("yygg-")));
+
+        // Both `b`s were revealed, so one is not enough.
+        let err = word.check(&String::from("axbex")).unwrap_err();
+        assert_eq!(err, WordError::MustUse('b'));
+        assert!(word.check(&String::from("abbex")).is_ok());
+    }
+
+    #[test]
+    fn rejected_guesses_reveal_nothing() {
+        let mut word = Word::new("crane", true);
+        word.check(&String::from("punky")).unwrap();
+        assert!(word.check(&String::from("cloud")).is_err());
+
+        // `cloud` was refused, so its green `c` is not required.
+        assert!(word.check(&String::from("snout")).is_ok());
+    }
+
+    #[test]
+    fn solved_only_when_every_letter_is_correct() {
+        let word = Word::new("crane", false);
+
+        assert!(word.is_solved(&tiles("ggggg")));
+        assert!(!word.is_solved(&tiles("gggg-")));
+        assert!(!word.is_solved(&tiles("yyyyy")));
+    }
+}
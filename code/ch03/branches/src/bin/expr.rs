@@ -0,0 +1,83 @@
+use branches::Program;
+use std::io::{self, Read};
+use std::{env, fs, process};
+
+struct Config {
+    /// Where the source came from, for messages.
+    name: String,
+    source: String,
+    /// Only type-check; do not run.
+    check: bool,
+}
+
+impl Config {
+    fn build(mut args: impl Iterator<Item = String>) -> Result<Config, String> {
+        args.next();
+
+        let mut path = None;
+        let mut inline = None;
+        let mut check = false;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-e" => inline = Some(args.next().ok_or("missing value for -e")?),
+                "--check" => check = true,
+                _ if arg.starts_with('-') && arg != "-" => {
+                    return Err(format!("unknown argument: {arg}"))
+                }
+                _ if path.is_some() => return Err(format!("unexpected argument: {arg}")),
+                _ => path = Some(arg),
+            }
+        }
+
+        let (name, source) = match (inline, path) {
+            (Some(_), Some(_)) => return Err(String::from("give either FILE or -e, not both")),
+            (Some(source), None) => (String::from("<expr>"), source),
+            (None, Some(path)) if path != "-" => {
+                let source = fs::read_to_string(&path)
+                    .map_err(|err| format!("cannot read {path}: {err}"))?;
+                (path, source)
+            }
+            (None, _) => {
+                let mut source = String::new();
+                io::stdin()
+                    .read_to_string(&mut source)
+                    .map_err(|err| format!("cannot read stdin: {err}"))?;
+                (String::from("<stdin>"), source)
+            }
+        };
+
+        Ok(Config {
+            name,
+            source,
+            check,
+        })
+    }
+}
+
+fn main() {
+    let config = Config::build(env::args()).unwrap_or_else(|err| {
+        eprintln!("Problem parsing arguments: {err}");
+        eprintln!("Usage: expr [FILE | -e SOURCE] [--check]");
+        eprintln!("Reads stdin without a FILE; try programs/listing.expr.");
+        process::exit(2);
+    });
+
+    let program = Program::compile(&config.source).unwrap_or_else(|diagnostic| {
+        eprint!("{}", diagnostic.render(&config.source, &config.name));
+        process::exit(1);
+    });
+
+    if config.check {
+        println!("ok: {}", program.ty());
+        return;
+    }
+
+    match program.run() {
+        Ok(value) => println!("{value}"),
+        Err(diagnostic) => {
+            eprint!("{}", diagnostic.render(&config.source, &config.name));
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,233 @@
+use std::error::Error;
+use std::fmt;
+
+/// A range of bytes in the source, `start` inclusive and `end` exclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Span {
+        Span { start, end }
+    }
+
+    /// From the start of `self` to the end of `other`.
+    pub fn to(self, other: Span) -> Span {
+        Span::new(self.start, other.end)
+    }
+}
+
+/// A note attached to part of the source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Label {
+    pub span: Span,
+    pub message: String,
+    /// Drawn with `^` rather than `-`; the location shown is the primary
+    /// label's.
+    pub primary: bool,
+}
+
+/// An error pointing into the source, rendered the way rustc does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// A rustc error code, such as `E0308`, when the error matches one.
+    pub code: Option<&'static str>,
+    pub message: String,
+    pub labels: Vec<Label>,
+    pub notes: Vec<String>,
+}
+
+impl Diagnostic {
+    pub fn error(message: impl Into<String>) -> Diagnostic {
+        Diagnostic {
+            code: None,
+            message: message.into(),
+            labels: Vec::new(),
+            notes: Vec::new(),
+        }
+    }
+
+    pub fn with_code(mut self, code: &'static str) -> Diagnostic {
+        self.code = Some(code);
+        self
+    }
+
+    pub fn with_primary(mut self, span: Span, message: impl Into<String>) -> Diagnostic {
+        self.labels.push(Label {
+            span,
+            message: message.into(),
+            primary: true,
+        });
+        self
+    }
+
+    pub fn with_secondary(mut self, span: Span, message: impl Into<String>) -> Diagnostic {
+        self.labels.push(Label {
+            span,
+            message: message.into(),
+            primary: false,
+        });
+        self
+    }
+
+    pub fn with_note(mut self, note: impl Into<String>) -> Diagnostic {
+        self.notes.push(note.into());
+        self
+    }
+
+    /// The message, the location and the labelled source lines, such as:
+    ///
+    /// ```text
+    /// error[E0308]: `if` and `else` have incompatible types
+    ///  --> listing.rs:2:40
+    ///   |
+    /// 2 | let number = if condition { 5 } else { "six" };
+    ///   |                             -          ^^^^^ expected integer, found `&str`
+    ///   |                             |
+    ///   |                             expected because of this
+    /// ```
+    pub fn render(&self, source: &str, name: &str) -> String {
+        let mut output = format!("{self}\n");
+
+        let mut labels: Vec<(Location, &Label)> = self
+            .labels
+            .iter()
+            .map(|label| (Location::of(source, label.span), label))
+            .collect();
+        labels.sort_by_key(|(location, _)| (location.line, location.column));
+
+        let width = labels
+            .iter()
+            .map(|(location, _)| location.line.to_string().len())
+            .max()
+            .unwrap_or(1);
+        let gutter = " ".repeat(width);
+
+        if let Some((location, _)) = labels
+            .iter()
+            .find(|(_, label)| label.primary)
+            .or(labels.first())
+        {
+            output += &format!("{gutter}--> {name}:{}:{}\n", location.line, location.column);
+            output += &format!("{gutter} |\n");
+        }
+
+        let mut start = 0;
+        while start < labels.len() {
+            let line = labels[start].0.line;
+            let end =
+                start + labels[start..].partition_point(|(location, _)| location.line == line);
+            let group = &labels[start..end];
+
+            let text = source.lines().nth(line - 1).unwrap_or_default();
+            output += &format!("{line:>width$} | {}\n", text.replace('\t', " "));
+            for row in label_rows(group) {
+                output += &format!("{gutter} | {}\n", row.trim_end());
+            }
+
+            start = end;
+        }
+
+        for note in &self.notes {
+            output += &format!("{gutter} = note: {note}\n");
+        }
+
+        output
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.code {
+            Some(code) => write!(f, "error[{code}]: {}", self.message),
+            None => write!(f, "error: {}", self.message),
+        }
+    }
+}
+
+impl Error for Diagnostic {}
+
+/// Where a span starts, counting lines and columns from 1, and how many
+/// characters of its first line it covers.
+#[derive(Debug, Clone, Copy)]
+struct Location {
+    line: usize,
+    column: usize,
+    length: usize,
+}
+
+impl Location {
+    fn of(source: &str, span: Span) -> Location {
+        let start = span.start.min(source.len());
+        let before = &source[..start];
+        let line_start = before.rfind('\n').map_or(0, |at| at + 1);
+        let line_end = source[start..]
+            .find('\n')
+            .map_or(source.len(), |at| start + at);
+        let end = span.end.clamp(start, line_end);
+
+        Location {
+            line: before.matches('\n').count() + 1,
+            column: source[line_start..start].chars().count() + 1,
+            // Even an empty span, such as the end of the input, gets a mark.
+            length: source[start..end].chars().count().max(1),
+        }
+    }
+}
+
+/// The rows under one source line: every label's marks, the rightmost
+/// label's message beside them, then the others' messages hanging below.
+fn label_rows(group: &[(Location, &Label)]) -> Vec<String> {
+    // Later labels draw over earlier ones where they overlap.
+    let mut marks: Vec<char> = Vec::new();
+    for (location, label) in group {
+        let column = location.column - 1;
+        let end = column + location.length;
+        if marks.len() < end {
+            marks.resize(end, ' ');
+        }
+        let mark = if label.primary { '^' } else { '-' };
+        marks[column..end].fill(mark);
+    }
+    let mut marks: String = marks.into_iter().collect();
+
+    let Some(((_, last), rest)) = group.split_last() else {
+        return Vec::new();
+    };
+    if !last.message.is_empty() {
+        marks += &format!(" {}", last.message);
+    }
+
+    let mut rows = vec![marks];
+    for index in (0..rest.len()).rev() {
+        let hanging: Vec<usize> = rest[..=index]
+            .iter()
+            .map(|(location, _)| location.column - 1)
+            .collect();
+        rows.push(bars(&hanging, None));
+
+        let (location, label) = rest[index];
+        rows.push(bars(
+            &hanging[..index],
+            Some((location.column - 1, &label.message)),
+        ));
+    }
+
+    rows
+}
+
+/// A row of `|` at each column, optionally ending with a message.
+fn bars(columns: &[usize], message: Option<(usize, &str)>) -> String {
+    let mut row = String::new();
+    for &column in columns {
+        row += &" ".repeat(column.saturating_sub(row.chars().count()));
+        row.push('|');
+    }
+    if let Some((column, message)) = message {
+        row += &" ".repeat(column.saturating_sub(row.chars().count()));
+        row += message;
+    }
+    row
+}
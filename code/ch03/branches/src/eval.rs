@@ -0,0 +1,321 @@
+use crate::diagnostic::{Diagnostic, Span};
+use crate::syntax::{self, BinaryOp, Block, Expr, ExprKind, Stmt, UnaryOp};
+use crate::types::{self, Type};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Int(i64),
+    Str(String),
+    Bool(bool),
+    Unit,
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Int(value) => write!(f, "{value}"),
+            Value::Str(text) => write!(f, "{text}"),
+            Value::Bool(value) => write!(f, "{value}"),
+            Value::Unit => write!(f, "()"),
+        }
+    }
+}
+
+/// A program that parsed and type-checked, so running it can only fail
+/// the ways a well-typed Rust program can: overflow and division by zero.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Program {
+    block: Block,
+    ty: Type,
+}
+
+impl Program {
+    pub fn compile(source: &str) -> Result<Program, Diagnostic> {
+        let block = syntax::parse(source)?;
+        let ty = types::check(&block)?;
+
+        Ok(Program { block, ty })
+    }
+
+    /// The type of the program's value.
+    pub fn ty(&self) -> Type {
+        self.ty
+    }
+
+    pub fn run(&self) -> Result<Value, Diagnostic> {
+        let mut interpreter = Interpreter {
+            variables: Vec::new(),
+        };
+        interpreter.block(&self.block)
+    }
+}
+
+/// A runtime error, in the words of the panic Rust would raise.
+fn panic(span: Span, message: &str) -> Diagnostic {
+    Diagnostic::error(format!("this would panic: {message}")).with_primary(span, message)
+}
+
+struct Interpreter {
+    variables: Vec<(String, Value)>,
+}
+
+impl Interpreter {
+    fn block(&mut self, block: &Block) -> Result<Value, Diagnostic> {
+        let outer = self.variables.len();
+
+        for stmt in &block.stmts {
+            match stmt {
+                Stmt::Let { name, value, .. } => {
+                    let value = self.expr(value)?;
+                    self.variables.push((name.clone(), value));
+                }
+                Stmt::Expr(expr) => {
+                    self.expr(expr)?;
+                }
+            }
+        }
+        let value = match &block.tail {
+            Some(tail) => self.expr(tail)?,
+            None => Value::Unit,
+        };
+
+        self.variables.truncate(outer);
+        Ok(value)
+    }
+
+    fn int(&mut self, expr: &Expr) -> Result<i64, Diagnostic> {
+        match self.expr(expr)? {
+            Value::Int(value) => Ok(value),
+            other => unreachable!("type checking let {other:?} through as an integer"),
+        }
+    }
+
+    fn bool(&mut self, expr: &Expr) -> Result<bool, Diagnostic> {
+        match self.expr(expr)? {
+            Value::Bool(value) => Ok(value),
+            other => unreachable!("type checking let {other:?} through as a bool"),
+        }
+    }
+
+    fn expr(&mut self, expr: &Expr) -> Result<Value, Diagnostic> {
+        let value = match &expr.kind {
+            ExprKind::Int(value) => Value::Int(*value),
+            ExprKind::Str(text) => Value::Str(text.clone()),
+            ExprKind::Bool(value) => Value::Bool(*value),
+            ExprKind::Var(name) => self
+                .variables
+                .iter()
+                .rev()
+                .find(|(variable, _)| variable == name)
+                .map(|(_, value)| value.clone())
+                .expect("type checking found every variable"),
+            ExprKind::Unary(UnaryOp::Negate, operand) => {
+                let value = self.int(operand)?;
+                Value::Int(
+                    value
+                        .checked_neg()
+                        .ok_or_else(|| panic(expr.span, "attempt to negate with overflow"))?,
+                )
+            }
+            ExprKind::Unary(UnaryOp::Not, operand) => Value::Bool(!self.bool(operand)?),
+            ExprKind::Binary(op, left, right) => self.binary(expr.span, *op, left, right)?,
+            ExprKind::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                if self.bool(condition)? {
+                    self.block(then_branch)?
+                } else if let Some(else_branch) = else_branch {
+                    self.expr(else_branch)?
+                } else {
+                    Value::Unit
+                }
+            }
+            ExprKind::Block(block) => self.block(block)?,
+        };
+
+        Ok(value)
+    }
+
+    fn binary(
+        &mut self,
+        span: Span,
+        op: BinaryOp,
+        left: &Expr,
+        right: &Expr,
+    ) -> Result<Value, Diagnostic> {
+        // `&&` and `||` only evaluate the right side when they need it.
+        match op {
+            BinaryOp::And => return Ok(Value::Bool(self.bool(left)? && self.bool(right)?)),
+            BinaryOp::Or => return Ok(Value::Bool(self.bool(left)? || self.bool(right)?)),
+            _ => {}
+        }
+
+        let left = self.expr(left)?;
+        let right = self.expr(right)?;
+
+        if op.is_comparison() {
+            let ordering = match (&left, &right) {
+                (Value::Int(a), Value::Int(b)) => a.cmp(b),
+                (Value::Str(a), Value::Str(b)) => a.cmp(b),
+                (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+                (Value::Unit, Value::Unit) => std::cmp::Ordering::Equal,
+                _ => unreachable!("type checking compared {left:?} with {right:?}"),
+            };
+            let result = match op {
+                BinaryOp::Equal => ordering.is_eq(),
+                BinaryOp::NotEqual => ordering.is_ne(),
+                BinaryOp::Less => ordering.is_lt(),
+                BinaryOp::LessEqual => ordering.is_le(),
+                BinaryOp::Greater => ordering.is_gt(),
+                _ => ordering.is_ge(),
+            };
+            return Ok(Value::Bool(result));
+        }
+
+        let (Value::Int(a), Value::Int(b)) = (&left, &right) else {
+            unreachable!(
+                "type checking let {left:?} {} {right:?} through",
+                op.symbol()
+            );
+        };
+        let (a, b) = (*a, *b);
+
+        let result = match op {
+            BinaryOp::Add => a.checked_add(b),
+            BinaryOp::Subtract => a.checked_sub(b),
+            BinaryOp::Multiply => a.checked_mul(b),
+            BinaryOp::Divide | BinaryOp::Remainder if b == 0 => {
+                let message = if op == BinaryOp::Divide {
+                    "attempt to divide by zero"
+                } else {
+                    "attempt to calculate the remainder with a divisor of zero"
+                };
+                return Err(panic(span, message));
+            }
+            BinaryOp::Divide => a.checked_div(b),
+            _ => a.checked_rem(b),
+        };
+
+        result.map(Value::Int).ok_or_else(|| {
+            let verb = match op {
+                BinaryOp::Add => "add",
+                BinaryOp::Subtract => "subtract",
+                BinaryOp::Multiply => "multiply",
+                BinaryOp::Divide => "divide",
+                _ => "calculate the remainder",
+            };
+            panic(span, &format!("attempt to {verb} with overflow"))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(source: &str) -> Result<Value, Diagnostic> {
+        Program::compile(source)?.run()
+    }
+
+    #[test]
+    fn runs_the_chapter_programs() {
+        let fixed = Program::compile(include_str!("../programs/fixed.expr")).unwrap();
+        assert_eq!(fixed.ty(), Type::Int);
+        assert_eq!(fixed.run(), Ok(Value::Int(5)));
+
+        assert_eq!(
+            run(include_str!("../programs/fizzbuzz.expr")),
+            Ok(Value::Str(String::from("FizzBuzz")))
+        );
+        assert!(Program::compile(include_str!("../programs/listing.expr")).is_err());
+    }
+
+    #[test]
+    fn evaluates_arithmetic_and_comparisons() {
+        assert_eq!(run("1 + 2 * 3 - 8 / 2 % 3"), Ok(Value::Int(6)));
+        assert_eq!(run("-7 / 2"), Ok(Value::Int(-3)));
+        assert_eq!(run("-7 % 2"), Ok(Value::Int(-1)));
+        assert_eq!(run("\"apple\" < \"banana\""), Ok(Value::Bool(true)));
+        assert_eq!(run("false < true"), Ok(Value::Bool(true)));
+        assert_eq!(run("{ 1; } == { 2; }"), Ok(Value::Bool(true)));
+        assert_eq!(run("3 >= 4 || !(2 != 2)"), Ok(Value::Bool(true)));
+    }
+
+    #[test]
+    fn picks_the_branch_the_condition_chooses() {
+        let fizzbuzz = |n: i64| {
+            let source = include_str!("../programs/fizzbuzz.expr")
+                .replace("let n = 15;", &format!("let n = {n};"));
+            run(&source).unwrap().to_string()
+        };
+
+        assert_eq!(fizzbuzz(9), "Fizz");
+        assert_eq!(fizzbuzz(10), "Buzz");
+        assert_eq!(fizzbuzz(7), "a number");
+        assert_eq!(run("if false { 1; }"), Ok(Value::Unit));
+    }
+
+    #[test]
+    fn later_bindings_shadow_earlier_ones() {
+        assert_eq!(
+            run("let x = 5; let x = x + 1; { let x = x * 2; x } + x"),
+            Ok(Value::Int(18))
+        );
+    }
+
+    #[test]
+    fn runs_the_longest_chains_allowed() {
+        let chain = format!("1{}", " + 1".repeat(crate::syntax::MAX_DEPTH - 1));
+        assert_eq!(run(&chain), Ok(Value::Int(crate::syntax::MAX_DEPTH as i64)));
+    }
+
+    #[test]
+    fn short_circuits_logic() {
+        // The right side would divide by zero if it ran.
+        assert_eq!(run("false && 1 / 0 == 0"), Ok(Value::Bool(false)));
+        assert_eq!(run("true || 1 / 0 == 0"), Ok(Value::Bool(true)));
+    }
+
+    #[test]
+    fn panics_where_rust_would() {
+        let cases = [
+            ("9223372036854775807 + 1", "attempt to add with overflow"),
+            (
+                "-9223372036854775807 - 2",
+                "attempt to subtract with overflow",
+            ),
+            (
+                "4611686018427387904 * 2",
+                "attempt to multiply with overflow",
+            ),
+            ("1 / 0", "attempt to divide by zero"),
+            (
+                "1 % 0",
+                "attempt to calculate the remainder with a divisor of zero",
+            ),
+            (
+                "let m = -9223372036854775807 - 1; -m",
+                "attempt to negate with overflow",
+            ),
+            (
+                "let m = -9223372036854775807 - 1; m / -1",
+                "attempt to divide with overflow",
+            ),
+        ];
+
+        for (source, message) in cases {
+            let err = run(source).unwrap_err();
+            assert_eq!(
+                err.message,
+                format!("this would panic: {message}"),
+                "{source}"
+            );
+        }
+
+        let err = run("let a = 1;\nlet b = a / 0;").unwrap_err();
+        assert_eq!(err.labels[0].span, Span::new(19, 24));
+    }
+}
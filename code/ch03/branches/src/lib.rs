@@ -0,0 +1,11 @@
+//! A tiny expression language with Rust's rules for `if`, so the error in
+//! this chapter's listing can be seen, fixed and run.
+
+pub mod diagnostic;
+pub mod eval;
+pub mod syntax;
+pub mod types;
+
+pub use crate::diagnostic::{Diagnostic, Span};
+pub use crate::eval::{Program, Value};
+pub use crate::types::Type;
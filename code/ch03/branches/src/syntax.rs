@@ -0,0 +1,748 @@
+use crate::diagnostic::{Diagnostic, Span};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Token {
+    Int(i64),
+    Str(String),
+    Ident(String),
+    Let,
+    If,
+    Else,
+    True,
+    False,
+    LeftParen,
+    RightParen,
+    LeftBrace,
+    RightBrace,
+    Semicolon,
+    Assign,
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Percent,
+    Bang,
+    EqualEqual,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    AndAnd,
+    OrOr,
+    Eof,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            Token::Int(value) => return write!(f, "`{value}`"),
+            Token::Str(text) => return write!(f, "`{text:?}`"),
+            Token::Ident(name) => return write!(f, "`{name}`"),
+            Token::Eof => return write!(f, "end of input"),
+            Token::Let => "let",
+            Token::If => "if",
+            Token::Else => "else",
+            Token::True => "true",
+            Token::False => "false",
+            Token::LeftParen => "(",
+            Token::RightParen => ")",
+            Token::LeftBrace => "{",
+            Token::RightBrace => "}",
+            Token::Semicolon => ";",
+            Token::Assign => "=",
+            Token::Plus => "+",
+            Token::Minus => "-",
+            Token::Star => "*",
+            Token::Slash => "/",
+            Token::Percent => "%",
+            Token::Bang => "!",
+            Token::EqualEqual => "==",
+            Token::NotEqual => "!=",
+            Token::Less => "<",
+            Token::LessEqual => "<=",
+            Token::Greater => ">",
+            Token::GreaterEqual => ">=",
+            Token::AndAnd => "&&",
+            Token::OrOr => "||",
+        };
+        write!(f, "`{text}`")
+    }
+}
+
+/// Splits `source` into tokens, ending with `Token::Eof`. `//` starts a
+/// comment that runs to the end of the line.
+pub fn tokenize(source: &str) -> Result<Vec<(Token, Span)>, Diagnostic> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        let mut end = start + c.len_utf8();
+        let mut next_is = |expected: char| match chars.peek() {
+            Some(&(at, next)) if next == expected => {
+                chars.next();
+                end = at + next.len_utf8();
+                true
+            }
+            _ => false,
+        };
+
+        let token = match c {
+            _ if c.is_whitespace() => continue,
+            '/' if next_is('/') => {
+                while chars.next_if(|&(_, c)| c != '\n').is_some() {}
+                continue;
+            }
+            '(' => Token::LeftParen,
+            ')' => Token::RightParen,
+            '{' => Token::LeftBrace,
+            '}' => Token::RightBrace,
+            ';' => Token::Semicolon,
+            '+' => Token::Plus,
+            '-' => Token::Minus,
+            '*' => Token::Star,
+            '/' => Token::Slash,
+            '%' => Token::Percent,
+            '=' if next_is('=') => Token::EqualEqual,
+            '=' => Token::Assign,
+            '!' if next_is('=') => Token::NotEqual,
+            '!' => Token::Bang,
+            '<' if next_is('=') => Token::LessEqual,
+            '<' => Token::Less,
+            '>' if next_is('=') => Token::GreaterEqual,
+            '>' => Token::Greater,
+            '&' if next_is('&') => Token::AndAnd,
+            '|' if next_is('|') => Token::OrOr,
+            '"' => {
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some((at, '"')) => {
+                            end = at + 1;
+                            break;
+                        }
+                        Some((at, '\\')) => match chars.next() {
+                            Some((_, 'n')) => text.push('\n'),
+                            Some((_, 't')) => text.push('\t'),
+                            Some((_, c @ ('"' | '\\'))) => text.push(c),
+                            _ => {
+                                return Err(Diagnostic::error("unknown character escape")
+                                    .with_primary(Span::new(at, at + 2), "unknown escape"))
+                            }
+                        },
+                        Some((_, c)) => text.push(c),
+                        None => {
+                            return Err(Diagnostic::error("unterminated double quote string")
+                                .with_code("E0765")
+                                .with_primary(Span::new(start, source.len()), ""))
+                        }
+                    }
+                }
+                Token::Str(text)
+            }
+            '0'..='9' => {
+                while let Some((at, digit)) =
+                    chars.next_if(|&(_, c)| c.is_ascii_digit() || c == '_')
+                {
+                    end = at + digit.len_utf8();
+                }
+                let digits = source[start..end].replace('_', "");
+                let value = digits.parse().map_err(|_| {
+                    Diagnostic::error("integer literal is too large")
+                        .with_primary(Span::new(start, end), "")
+                        .with_note(format!("integers go up to {}", i64::MAX))
+                })?;
+                Token::Int(value)
+            }
+            _ if c == '_' || c.is_alphabetic() => {
+                while let Some((at, c)) = chars.next_if(|&(_, c)| c == '_' || c.is_alphanumeric()) {
+                    end = at + c.len_utf8();
+                }
+                match &source[start..end] {
+                    "let" => Token::Let,
+                    "if" => Token::If,
+                    "else" => Token::Else,
+                    "true" => Token::True,
+                    "false" => Token::False,
+                    name => Token::Ident(name.to_string()),
+                }
+            }
+            _ => {
+                return Err(Diagnostic::error(format!("unknown start of token: {c}"))
+                    .with_primary(Span::new(start, end), ""))
+            }
+        };
+
+        tokens.push((token, Span::new(start, end)));
+    }
+
+    tokens.push((Token::Eof, Span::new(source.len(), source.len())));
+    Ok(tokens)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOp {
+    Negate,
+    Not,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    And,
+    Or,
+}
+
+impl BinaryOp {
+    pub fn symbol(self) -> &'static str {
+        match self {
+            BinaryOp::Add => "+",
+            BinaryOp::Subtract => "-",
+            BinaryOp::Multiply => "*",
+            BinaryOp::Divide => "/",
+            BinaryOp::Remainder => "%",
+            BinaryOp::Equal => "==",
+            BinaryOp::NotEqual => "!=",
+            BinaryOp::Less => "<",
+            BinaryOp::LessEqual => "<=",
+            BinaryOp::Greater => ">",
+            BinaryOp::GreaterEqual => ">=",
+            BinaryOp::And => "&&",
+            BinaryOp::Or => "||",
+        }
+    }
+
+    pub fn is_arithmetic(self) -> bool {
+        matches!(
+            self,
+            BinaryOp::Add
+                | BinaryOp::Subtract
+                | BinaryOp::Multiply
+                | BinaryOp::Divide
+                | BinaryOp::Remainder
+        )
+    }
+
+    pub fn is_comparison(self) -> bool {
+        matches!(
+            self,
+            BinaryOp::Equal
+                | BinaryOp::NotEqual
+                | BinaryOp::Less
+                | BinaryOp::LessEqual
+                | BinaryOp::Greater
+                | BinaryOp::GreaterEqual
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expr {
+    pub kind: ExprKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExprKind {
+    Int(i64),
+    Str(String),
+    Bool(bool),
+    Var(String),
+    Unary(UnaryOp, Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+    /// `else if` is an `If` as the whole `else_branch`.
+    If {
+        condition: Box<Expr>,
+        then_branch: Block,
+        else_branch: Option<Box<Expr>>,
+    },
+    Block(Block),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Stmt {
+    Let {
+        name: String,
+        name_span: Span,
+        value: Expr,
+    },
+    /// An expression whose value is thrown away.
+    Expr(Expr),
+}
+
+/// Statements and an optional final expression, which is the block's value.
+/// A whole program is a block without the braces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block {
+    pub stmts: Vec<Stmt>,
+    pub tail: Option<Box<Expr>>,
+    pub span: Span,
+}
+
+impl Block {
+    /// Where the block's value comes from: the final expression, or the
+    /// whole block when it has none.
+    pub fn value_span(&self) -> Span {
+        match &self.tail {
+            Some(tail) => tail.span,
+            None => self.span,
+        }
+    }
+}
+
+/// How deeply parentheses, blocks, `if`s and unary operators may nest.
+/// The parser recurses through every precedence level for each of them,
+/// so this stays low enough for a thread's default stack.
+pub const MAX_NESTING: usize = 64;
+
+/// How deep the finished tree may be, where every operator in a chain
+/// such as `1 + 2 + 3` is a level too. Type checking and evaluation
+/// recurse over the tree, so without a limit a long enough chain would
+/// overflow the stack.
+pub const MAX_DEPTH: usize = 256;
+
+/// Parses a whole program.
+pub fn parse(source: &str) -> Result<Block, Diagnostic> {
+    let tokens = tokenize(source)?;
+    let mut parser = Parser {
+        tokens,
+        position: 0,
+        nesting: 0,
+        depth: 0,
+    };
+
+    let block = parser.block_body(0)?;
+    parser.expect(Token::Eof)?;
+    Ok(block)
+}
+
+fn too_deep(span: Span, label: String) -> Diagnostic {
+    Diagnostic::error("expression is nested too deeply")
+        .with_primary(span, label)
+        .with_note("split it up with `let`")
+}
+
+struct Parser {
+    tokens: Vec<(Token, Span)>,
+    position: usize,
+    nesting: usize,
+    /// How deep the tree built so far is, counting operators in chains.
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.position].0
+    }
+
+    fn span(&self) -> Span {
+        self.tokens[self.position].1
+    }
+
+    /// The span of the token just consumed.
+    fn previous_span(&self) -> Span {
+        self.tokens[self.position.saturating_sub(1)].1
+    }
+
+    fn advance(&mut self) -> (Token, Span) {
+        let token = self.tokens[self.position].clone();
+        if token.0 != Token::Eof {
+            self.position += 1;
+        }
+        token
+    }
+
+    fn eat(&mut self, token: Token) -> bool {
+        if *self.peek() == token {
+            self.advance();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn unexpected(&self, expected: &str) -> Diagnostic {
+        Diagnostic::error(format!("expected {expected}, found {}", self.peek()))
+            .with_primary(self.span(), format!("expected {expected}"))
+    }
+
+    fn expect(&mut self, token: Token) -> Result<Span, Diagnostic> {
+        if *self.peek() == token {
+            Ok(self.advance().1)
+        } else {
+            let expected = match token {
+                Token::Eof => String::from("end of input"),
+                _ => token.to_string(),
+            };
+            Err(self.unexpected(&expected))
+        }
+    }
+
+    /// Goes one level deeper in the tree, failing at the token that would
+    /// pass `MAX_DEPTH`.
+    fn deepen(&mut self) -> Result<(), Diagnostic> {
+        if self.depth == MAX_DEPTH {
+            return Err(too_deep(
+                self.span(),
+                format!("more than {MAX_DEPTH} levels deep, counting each operator"),
+            ));
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    /// Runs `parse` one level of nesting deeper.
+    fn nested<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Parser) -> Result<T, Diagnostic>,
+    ) -> Result<T, Diagnostic> {
+        if self.nesting == MAX_NESTING {
+            return Err(too_deep(
+                self.span(),
+                format!("more than {MAX_NESTING} levels of nesting"),
+            ));
+        }
+        self.deepen()?;
+        self.nesting += 1;
+
+        let result = parse(self)?;
+        self.nesting -= 1;
+        self.depth -= 1;
+        Ok(result)
+    }
+
+    /// Statements up to a `}` or the end of input, which is left unread.
+    fn block_body(&mut self, start: usize) -> Result<Block, Diagnostic> {
+        let mut stmts = Vec::new();
+        let mut tail = None;
+
+        while !matches!(self.peek(), Token::RightBrace | Token::Eof) {
+            if self.eat(Token::Let) {
+                let (name, name_span) = match self.advance() {
+                    (Token::Ident(name), span) => (name, span),
+                    _ => {
+                        self.position -= 1;
+                        return Err(self.unexpected("a name"));
+                    }
+                };
+                self.expect(Token::Assign)?;
+                let value = self.expression()?;
+                self.expect(Token::Semicolon)?;
+
+                stmts.push(Stmt::Let {
+                    name,
+                    name_span,
+                    value,
+                });
+                continue;
+            }
+
+            let expr = self.expression()?;
+            let block_like = matches!(expr.kind, ExprKind::If { .. } | ExprKind::Block(_));
+
+            if self.eat(Token::Semicolon) {
+                stmts.push(Stmt::Expr(expr));
+            } else if matches!(self.peek(), Token::RightBrace | Token::Eof) {
+                tail = Some(Box::new(expr));
+            } else if block_like {
+                // Like Rust, `if` and blocks need no `;` mid-block.
+                stmts.push(Stmt::Expr(expr));
+            } else {
+                return Err(self.unexpected("`;`"));
+            }
+        }
+
+        let end = match &tail {
+            Some(tail) => tail.span.end,
+            None => self.previous_span().end.max(start),
+        };
+        Ok(Block {
+            stmts,
+            tail,
+            span: Span::new(start, end),
+        })
+    }
+
+    fn block(&mut self) -> Result<Block, Diagnostic> {
+        let open = self.expect(Token::LeftBrace)?;
+        let mut block = self.block_body(open.start)?;
+        let close = self.expect(Token::RightBrace)?;
+
+        block.span = open.to(close);
+        Ok(block)
+    }
+
+    fn expression(&mut self) -> Result<Expr, Diagnostic> {
+        self.binary(0)
+    }
+
+    /// Precedence climbing; higher levels bind tighter. Comparisons do not
+    /// chain, as in Rust.
+    fn binary(&mut self, level: usize) -> Result<Expr, Diagnostic> {
+        const LEVELS: usize = 5;
+        if level == LEVELS {
+            return self.unary();
+        }
+
+        // Each operator makes the tree one level deeper on the left.
+        let outer = self.depth;
+        let mut left = self.binary(level + 1)?;
+        loop {
+            let op = match (level, self.peek()) {
+                (0, Token::OrOr) => BinaryOp::Or,
+                (1, Token::AndAnd) => BinaryOp::And,
+                (2, Token::EqualEqual) => BinaryOp::Equal,
+                (2, Token::NotEqual) => BinaryOp::NotEqual,
+                (2, Token::Less) => BinaryOp::Less,
+                (2, Token::LessEqual) => BinaryOp::LessEqual,
+                (2, Token::Greater) => BinaryOp::Greater,
+                (2, Token::GreaterEqual) => BinaryOp::GreaterEqual,
+                (3, Token::Plus) => BinaryOp::Add,
+                (3, Token::Minus) => BinaryOp::Subtract,
+                (4, Token::Star) => BinaryOp::Multiply,
+                (4, Token::Slash) => BinaryOp::Divide,
+                (4, Token::Percent) => BinaryOp::Remainder,
+                _ => {
+                    self.depth = outer;
+                    return Ok(left);
+                }
+            };
+            self.deepen()?;
+            let op_span = self.advance().1;
+
+            if op.is_comparison() {
+                if let ExprKind::Binary(previous, ..) = &left.kind {
+                    if previous.is_comparison() {
+                        return Err(Diagnostic::error("comparison operators cannot be chained")
+                            .with_primary(op_span, ""));
+                    }
+                }
+            }
+
+            let right = self.binary(level + 1)?;
+            let span = left.span.to(right.span);
+            left = Expr {
+                kind: ExprKind::Binary(op, Box::new(left), Box::new(right)),
+                span,
+            };
+        }
+    }
+
+    fn unary(&mut self) -> Result<Expr, Diagnostic> {
+        let op = match self.peek() {
+            Token::Minus => UnaryOp::Negate,
+            Token::Bang => UnaryOp::Not,
+            _ => return self.primary(),
+        };
+        let start = self.advance().1;
+        let operand = self.nested(Parser::unary)?;
+
+        let span = start.to(operand.span);
+        Ok(Expr {
+            kind: ExprKind::Unary(op, Box::new(operand)),
+            span,
+        })
+    }
+
+    fn primary(&mut self) -> Result<Expr, Diagnostic> {
+        let span = self.span();
+        let kind = match self.peek().clone() {
+            Token::Int(value) => ExprKind::Int(value),
+            Token::Str(text) => ExprKind::Str(text),
+            Token::True => ExprKind::Bool(true),
+            Token::False => ExprKind::Bool(false),
+            Token::Ident(name) => ExprKind::Var(name),
+            Token::LeftParen => {
+                self.advance();
+                let inner = self.nested(Parser::expression)?;
+                let close = self.expect(Token::RightParen)?;
+                return Ok(Expr {
+                    kind: inner.kind,
+                    span: span.to(close),
+                });
+            }
+            Token::LeftBrace => {
+                let block = self.nested(Parser::block)?;
+                let span = block.span;
+                return Ok(Expr {
+                    kind: ExprKind::Block(block),
+                    span,
+                });
+            }
+            Token::If => return self.nested(Parser::if_expression),
+            _ => return Err(self.unexpected("an expression")),
+        };
+
+        self.advance();
+        Ok(Expr { kind, span })
+    }
+
+    fn if_expression(&mut self) -> Result<Expr, Diagnostic> {
+        let start = self.expect(Token::If)?;
+        let condition = self.expression()?;
+        let then_branch = self.block()?;
+
+        let else_branch = if self.eat(Token::Else) {
+            let branch = if *self.peek() == Token::If {
+                self.nested(Parser::if_expression)?
+            } else {
+                let block = self.nested(Parser::block)?;
+                let span = block.span;
+                Expr {
+                    kind: ExprKind::Block(block),
+                    span,
+                }
+            };
+            Some(Box::new(branch))
+        } else {
+            None
+        };
+
+        let end = match &else_branch {
+            Some(branch) => branch.span,
+            None => then_branch.span,
+        };
+        Ok(Expr {
+            kind: ExprKind::If {
+                condition: Box::new(condition),
+                then_branch,
+                else_branch,
+            },
+            span: start.to(end),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(source: &str) -> Vec<Token> {
+        tokenize(source)
+            .unwrap()
+            .into_iter()
+            .map(|(token, _)| token)
+            .collect()
+    }
+
+    #[test]
+    fn tokenizes_operators_literals_and_comments() {
+        assert_eq!(
+            tokens("let x = 1_000 <= -y; // done\n\"a\\n\""),
+            [
+                Token::Let,
+                Token::Ident(String::from("x")),
+                Token::Assign,
+                Token::Int(1000),
+                Token::LessEqual,
+                Token::Minus,
+                Token::Ident(String::from("y")),
+                Token::Semicolon,
+                Token::Str(String::from("a\n")),
+                Token::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_bad_tokens() {
+        assert_eq!(tokenize("\"open").unwrap_err().code, Some("E0765"));
+        assert_eq!(
+            tokenize("99999999999999999999").unwrap_err().message,
+            "integer literal is too large"
+        );
+        assert_eq!(
+            tokenize("1 @ 2").unwrap_err().labels[0].span,
+            Span::new(2, 3)
+        );
+    }
+
+    #[test]
+    fn binds_tighter_operators_first() {
+        let program = parse("1 + 2 * 3 == 7 && !false").unwrap();
+        let tail = program.tail.unwrap();
+
+        let ExprKind::Binary(BinaryOp::And, left, right) = tail.kind else {
+            panic!("expected `&&` at the top, got {tail:?}");
+        };
+        assert!(matches!(left.kind, ExprKind::Binary(BinaryOp::Equal, ..)));
+        assert!(matches!(right.kind, ExprKind::Unary(UnaryOp::Not, _)));
+        assert_eq!(tail.span, Span::new(0, 24));
+    }
+
+    #[test]
+    fn rejects_chained_comparisons() {
+        let err = parse("1 < 2 < 3").unwrap_err();
+        assert_eq!(err.message, "comparison operators cannot be chained");
+        assert_eq!(err.labels[0].span, Span::new(6, 7));
+    }
+
+    #[test]
+    fn needs_semicolons_between_statements() {
+        assert!(parse("let a = 1; if true { 2 } else { 3 } a").is_ok());
+        assert_eq!(parse("1 2").unwrap_err().message, "expected `;`, found `2`");
+        assert_eq!(
+            parse("let = 1;").unwrap_err().message,
+            "expected a name, found `=`"
+        );
+    }
+
+    #[test]
+    fn allows_nesting_up_to_the_limit() {
+        let nesting = MAX_NESTING - 1;
+        let parens = format!("{}1{}", "(".repeat(nesting), ")".repeat(nesting));
+        let negations = format!("{}1", "-".repeat(nesting));
+        let chain = format!("1{}", " + 1".repeat(MAX_DEPTH - 1));
+
+        for source in [parens, negations, chain] {
+            assert!(parse(&source).is_ok(), "{source}");
+        }
+    }
+
+    #[test]
+    fn rejects_nesting_past_the_limit() {
+        let parens = format!("{}1{}", "(".repeat(50_000), ")".repeat(50_000));
+        let negations = format!("{}1", "-".repeat(50_000));
+        let blocks = format!("{}1{}", "{".repeat(50_000), "}".repeat(50_000));
+        let chain = format!("1{}", " * 1".repeat(50_000));
+        let ifs = format!("{}1{}", "if true { ".repeat(50_000), " }".repeat(50_000));
+        let else_ifs = format!("{} {{ 1 }}", "if true { 1 } else ".repeat(50_000));
+
+        for source in [parens, negations, blocks, ifs, else_ifs] {
+            let err = parse(&source).unwrap_err();
+            assert_eq!(err.message, "expression is nested too deeply");
+            assert_eq!(
+                err.labels[0].message,
+                format!("more than {MAX_NESTING} levels of nesting")
+            );
+        }
+
+        let err = parse(&chain).unwrap_err();
+        assert_eq!(
+            err.labels[0].message,
+            format!("more than {MAX_DEPTH} levels deep, counting each operator")
+        );
+        // The label points at the first operator past the limit.
+        assert_eq!(err.labels[0].span.start, 1 + 4 * MAX_DEPTH + 1);
+    }
+
+    #[test]
+    fn nesting_resets_between_expressions() {
+        let line = format!("let x = {}1;\n", "-".repeat(MAX_NESTING - 1));
+        assert!(parse(&line.repeat(10)).is_ok());
+
+        let sum = format!("({}) + ", "1 + ".repeat(MAX_DEPTH - 10) + "1").repeat(2) + "1";
+        assert!(parse(&sum).is_ok());
+    }
+}
@@ -0,0 +1,350 @@
+use crate::diagnostic::{Diagnostic, Span};
+use crate::syntax::{BinaryOp, Block, Expr, ExprKind, Stmt, UnaryOp};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Type {
+    Int,
+    Str,
+    Bool,
+    /// The value of a block with no final expression, or of an `if`
+    /// without an `else`.
+    Unit,
+}
+
+impl fmt::Display for Type {
+    /// Written the way rustc writes them in its messages.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Type::Int => "integer",
+            Type::Str => "`&str`",
+            Type::Bool => "`bool`",
+            Type::Unit => "`()`",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Checks a whole program and returns the type of its value.
+///
+/// Stops at the first error, as one mistake often causes several more.
+pub fn check(program: &Block) -> Result<Type, Diagnostic> {
+    let mut checker = Checker {
+        variables: Vec::new(),
+    };
+    checker.block(program)
+}
+
+fn mismatch(span: Span, expected: Type, found: Type) -> Diagnostic {
+    Diagnostic::error("mismatched types")
+        .with_code("E0308")
+        .with_primary(span, format!("expected {expected}, found {found}"))
+}
+
+struct Checker {
+    /// Every variable in scope, innermost last, so lookups from the end
+    /// find the binding that shadows the others.
+    variables: Vec<(String, Type)>,
+}
+
+impl Checker {
+    fn block(&mut self, block: &Block) -> Result<Type, Diagnostic> {
+        let outer = self.variables.len();
+
+        for stmt in &block.stmts {
+            match stmt {
+                Stmt::Let { name, value, .. } => {
+                    let ty = self.expr(value)?;
+                    self.variables.push((name.clone(), ty));
+                }
+                Stmt::Expr(expr) => {
+                    self.expr(expr)?;
+                }
+            }
+        }
+        let ty = match &block.tail {
+            Some(tail) => self.expr(tail)?,
+            None => Type::Unit,
+        };
+
+        self.variables.truncate(outer);
+        Ok(ty)
+    }
+
+    fn expect(&mut self, expr: &Expr, expected: Type) -> Result<(), Diagnostic> {
+        let found = self.expr(expr)?;
+        if found == expected {
+            Ok(())
+        } else {
+            Err(mismatch(expr.span, expected, found))
+        }
+    }
+
+    fn expr(&mut self, expr: &Expr) -> Result<Type, Diagnostic> {
+        match &expr.kind {
+            ExprKind::Int(_) => Ok(Type::Int),
+            ExprKind::Str(_) => Ok(Type::Str),
+            ExprKind::Bool(_) => Ok(Type::Bool),
+            ExprKind::Var(name) => self
+                .variables
+                .iter()
+                .rev()
+                .find(|(variable, _)| variable == name)
+                .map(|&(_, ty)| ty)
+                .ok_or_else(|| {
+                    Diagnostic::error(format!("cannot find value `{name}` in this scope"))
+                        .with_code("E0425")
+                        .with_primary(expr.span, "not found in this scope")
+                }),
+            ExprKind::Unary(op, operand) => {
+                let ty = match op {
+                    UnaryOp::Negate => Type::Int,
+                    UnaryOp::Not => Type::Bool,
+                };
+                self.expect(operand, ty)?;
+                Ok(ty)
+            }
+            ExprKind::Binary(op, left, right) => self.binary(*op, left, right),
+            ExprKind::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => self.if_expr(expr.span, condition, then_branch, else_branch.as_deref()),
+            ExprKind::Block(block) => self.block(block),
+        }
+    }
+
+    fn binary(&mut self, op: BinaryOp, left: &Expr, right: &Expr) -> Result<Type, Diagnostic> {
+        if op.is_arithmetic() {
+            for operand in [left, right] {
+                let ty = self.expr(operand)?;
+                if ty != Type::Int {
+                    return Err(Diagnostic::error(format!(
+                        "cannot apply binary operator `{}` to type {ty}",
+                        op.symbol()
+                    ))
+                    .with_code("E0369")
+                    .with_primary(operand.span, ty.to_string()));
+                }
+            }
+            return Ok(Type::Int);
+        }
+
+        if op.is_comparison() {
+            let expected = self.expr(left)?;
+            // Only `==` and `!=` work on every type; ordering `()` is
+            // pointless, but Rust allows it, so this does too.
+            self.expect(right, expected)?;
+            return Ok(Type::Bool);
+        }
+
+        self.expect(left, Type::Bool)?;
+        self.expect(right, Type::Bool)?;
+        Ok(Type::Bool)
+    }
+
+    fn if_expr(
+        &mut self,
+        span: Span,
+        condition: &Expr,
+        then_branch: &Block,
+        else_branch: Option<&Expr>,
+    ) -> Result<Type, Diagnostic> {
+        self.expect(condition, Type::Bool)?;
+        let then_type = self.block(then_branch)?;
+
+        let Some(else_branch) = else_branch else {
+            if then_type == Type::Unit {
+                return Ok(Type::Unit);
+            }
+            return Err(Diagnostic::error("`if` may be missing an `else` clause")
+                .with_code("E0317")
+                .with_primary(
+                    Span::new(span.start, span.start + "if".len()),
+                    format!("expected {then_type}, found `()`"),
+                )
+                .with_secondary(then_branch.value_span(), "found here")
+                .with_note("`if` expressions without `else` evaluate to `()`"));
+        };
+
+        let else_type = self.expr(else_branch)?;
+        if else_type == then_type {
+            return Ok(then_type);
+        }
+
+        // Point at the values themselves, not the braces around them.
+        let else_span = match &else_branch.kind {
+            ExprKind::Block(block) => block.value_span(),
+            _ => else_branch.span,
+        };
+        Err(Diagnostic::error("`if` and `else` have incompatible types")
+            .with_code("E0308")
+            .with_primary(
+                else_span,
+                format!("expected {then_type}, found {else_type}"),
+            )
+            .with_secondary(then_branch.value_span(), "expected because of this"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostic::Label;
+    use crate::syntax::parse;
+
+    fn check_source(source: &str) -> Result<Type, Diagnostic> {
+        check(&parse(source).unwrap())
+    }
+
+    /// The span of the `n`th (from 0) occurrence of `text` in `source`.
+    fn span_of(source: &str, text: &str, n: usize) -> Span {
+        let start = source.match_indices(text).nth(n).unwrap().0;
+        Span::new(start, start + text.len())
+    }
+
+    #[test]
+    fn gives_each_program_its_type() {
+        assert_eq!(check_source("1 + 2 * 3"), Ok(Type::Int));
+        assert_eq!(check_source("\"a\" == \"b\""), Ok(Type::Bool));
+        assert_eq!(check_source("let x = 5;"), Ok(Type::Unit));
+        assert_eq!(
+            check_source("if true { \"a\" } else { \"b\" }"),
+            Ok(Type::Str)
+        );
+        assert_eq!(check_source("if true { 1; }"), Ok(Type::Unit));
+        assert_eq!(check_source("let x = 1; let x = x == 1; x"), Ok(Type::Bool));
+        assert_eq!(
+            check_source(include_str!("../programs/fizzbuzz.expr")),
+            Ok(Type::Str)
+        );
+    }
+
+    #[test]
+    fn points_at_both_arms_of_incompatible_branches() {
+        let source = include_str!("../programs/listing.expr");
+        let err = check_source(source).unwrap_err();
+
+        assert_eq!(err.code, Some("E0308"));
+        assert_eq!(err.message, "`if` and `else` have incompatible types");
+        assert_eq!(
+            err.labels,
+            [
+                Label {
+                    span: span_of(source, "\"six\"", 0),
+                    message: String::from("expected integer, found `&str`"),
+                    primary: true,
+                },
+                Label {
+                    span: span_of(source, "5", 0),
+                    message: String::from("expected because of this"),
+                    primary: false,
+                },
+            ]
+        );
+        assert_eq!(
+            err.render(source, "listing.expr"),
+            "error[E0308]: `if` and `else` have incompatible types\n \
+             --> listing.expr:4:40\n  \
+             |\n\
+             4 | let number = if condition { 5 } else { \"six\" };\n  \
+             |                             -          ^^^^^ expected integer, found `&str`\n  \
+             |                             |\n  \
+             |                             expected because of this\n"
+        );
+    }
+
+    #[test]
+    fn else_if_arms_must_match_too() {
+        let source = "if true { 1 } else if false { 2 } else { true }";
+        let err = check_source(source).unwrap_err();
+
+        assert_eq!(err.message, "`if` and `else` have incompatible types");
+        assert_eq!(err.labels[0].span, span_of(source, "true", 1));
+        assert_eq!(err.labels[0].message, "expected integer, found `bool`");
+        assert_eq!(err.labels[1].span, span_of(source, "2", 0));
+    }
+
+    #[test]
+    fn if_without_else_must_be_unit() {
+        let source = "let x = if true { 3 };";
+        let err = check_source(source).unwrap_err();
+
+        assert_eq!(err.code, Some("E0317"));
+        assert_eq!(err.labels[0].span, span_of(source, "if", 0));
+        assert_eq!(err.labels[0].message, "expected integer, found `()`");
+        assert_eq!(err.labels[1].span, span_of(source, "3", 0));
+        assert_eq!(
+            err.notes,
+            ["`if` expressions without `else` evaluate to `()`"]
+        );
+    }
+
+    #[test]
+    fn conditions_and_logic_need_bools() {
+        let source = "if 1 { 2 } else { 3 }";
+        let err = check_source(source).unwrap_err();
+        assert_eq!(err.code, Some("E0308"));
+        assert_eq!(err.message, "mismatched types");
+        assert_eq!(err.labels[0].span, span_of(source, "1", 0));
+        assert_eq!(err.labels[0].message, "expected `bool`, found integer");
+
+        let err = check_source("true && \"yes\"").unwrap_err();
+        assert_eq!(err.labels[0].message, "expected `bool`, found `&str`");
+
+        let err = check_source("!5").unwrap_err();
+        assert_eq!(err.labels[0].message, "expected `bool`, found integer");
+
+        let err = check_source("-true").unwrap_err();
+        assert_eq!(err.labels[0].message, "expected integer, found `bool`");
+    }
+
+    #[test]
+    fn comparisons_need_matching_types() {
+        let source = "1 == \"one\"";
+        let err = check_source(source).unwrap_err();
+
+        assert_eq!(err.code, Some("E0308"));
+        assert_eq!(err.labels[0].span, span_of(source, "\"one\"", 0));
+        assert_eq!(err.labels[0].message, "expected integer, found `&str`");
+    }
+
+    #[test]
+    fn arithmetic_needs_integers() {
+        let source = "1 + \"two\"";
+        let err = check_source(source).unwrap_err();
+
+        assert_eq!(err.code, Some("E0369"));
+        assert_eq!(
+            err.message,
+            "cannot apply binary operator `+` to type `&str`"
+        );
+        assert_eq!(err.labels[0].span, span_of(source, "\"two\"", 0));
+
+        let err = check_source("true * 2").unwrap_err();
+        assert_eq!(
+            err.message,
+            "cannot apply binary operator `*` to type `bool`"
+        );
+    }
+
+    #[test]
+    fn variables_must_be_in_scope() {
+        let source = "let a = { let b = 1; b }; b";
+        let err = check_source(source).unwrap_err();
+
+        assert_eq!(err.code, Some("E0425"));
+        assert_eq!(err.message, "cannot find value `b` in this scope");
+        assert_eq!(err.labels[0].span, span_of(source, "b", 2));
+    }
+
+    #[test]
+    fn checks_the_deepest_trees_allowed() {
+        let nesting = crate::syntax::MAX_NESTING / 2 - 1;
+        let source = format!("{}true{}", "!(".repeat(nesting), ")".repeat(nesting));
+        assert_eq!(check_source(&source), Ok(Type::Bool));
+
+        let chain = format!("1{}", " + 1".repeat(crate::syntax::MAX_DEPTH - 1));
+        assert_eq!(check_source(&chain), Ok(Type::Int));
+    }
+}
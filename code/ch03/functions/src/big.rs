@@ -0,0 +1,169 @@
+use std::fmt;
+use std::ops::{Add, Mul, Sub};
+
+/// An unsigned integer of any size, just big enough for Fibonacci numbers.
+///
+/// Stored as base-2³² digits, least significant first, with no trailing
+/// zero digits, so zero is the empty vector.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct BigUint {
+    digits: Vec<u32>,
+}
+
+impl BigUint {
+    pub fn zero() -> BigUint {
+        BigUint { digits: Vec::new() }
+    }
+
+    pub fn one() -> BigUint {
+        BigUint::from(1u64)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    /// How many bits the number needs; zero needs none.
+    pub fn bits(&self) -> u64 {
+        match self.digits.last() {
+            Some(&top) => (self.digits.len() as u64 - 1) * 32 + u64::from(32 - top.leading_zeros()),
+            None => 0,
+        }
+    }
+
+    fn trim(mut self) -> BigUint {
+        while self.digits.last() == Some(&0) {
+            self.digits.pop();
+        }
+        self
+    }
+
+    /// Divides in place by a small divisor and returns the remainder.
+    fn div_rem_small(&mut self, divisor: u32) -> u32 {
+        let mut remainder = 0u64;
+
+        for digit in self.digits.iter_mut().rev() {
+            let value = (remainder << 32) | u64::from(*digit);
+            *digit = (value / u64::from(divisor)) as u32;
+            remainder = value % u64::from(divisor);
+        }
+
+        while self.digits.last() == Some(&0) {
+            self.digits.pop();
+        }
+        remainder as u32
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(value: u64) -> BigUint {
+        BigUint {
+            digits: vec![value as u32, (value >> 32) as u32],
+        }
+        .trim()
+    }
+}
+
+impl Add for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        let (long, short) = if self.digits.len() >= other.digits.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+
+        let mut digits = Vec::with_capacity(long.digits.len() + 1);
+        let mut carry = 0u64;
+        for (i, &digit) in long.digits.iter().enumerate() {
+            let sum = u64::from(digit) + u64::from(*short.digits.get(i).unwrap_or(&0)) + carry;
+            digits.push(sum as u32);
+            carry = sum >> 32;
+        }
+        if carry > 0 {
+            digits.push(carry as u32);
+        }
+
+        BigUint { digits }
+    }
+}
+
+/// # Panics
+///
+/// Panics if `other` is larger, since the result would be negative.
+impl Sub for &BigUint {
+    type Output = BigUint;
+
+    fn sub(self, other: &BigUint) -> BigUint {
+        let mut digits = Vec::with_capacity(self.digits.len());
+        let mut borrow = 0i64;
+
+        for (i, &digit) in self.digits.iter().enumerate() {
+            let mut difference =
+                i64::from(digit) - i64::from(*other.digits.get(i).unwrap_or(&0)) - borrow;
+            borrow = 0;
+            if difference < 0 {
+                difference += 1 << 32;
+                borrow = 1;
+            }
+            digits.push(difference as u32);
+        }
+
+        assert!(
+            borrow == 0 && other.digits.len() <= self.digits.len(),
+            "subtraction underflowed"
+        );
+        BigUint { digits }.trim()
+    }
+}
+
+/// Schoolbook multiplication: quadratic, but plenty for a few thousand digits.
+impl Mul for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: &BigUint) -> BigUint {
+        if self.is_zero() || other.is_zero() {
+            return BigUint::zero();
+        }
+
+        let mut digits = vec![0u32; self.digits.len() + other.digits.len()];
+        for (i, &a) in self.digits.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, &b) in other.digits.iter().enumerate() {
+                let product = u64::from(a) * u64::from(b) + u64::from(digits[i + j]) + carry;
+                digits[i + j] = product as u32;
+                carry = product >> 32;
+            }
+            digits[i + other.digits.len()] = carry as u32;
+        }
+
+        BigUint { digits }.trim()
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return f.pad_integral(true, "", "0");
+        }
+
+        // Peel off nine decimal digits at a time, least significant first.
+        let mut rest = self.clone();
+        let mut chunks = Vec::new();
+        while !rest.is_zero() {
+            chunks.push(rest.div_rem_small(1_000_000_000));
+        }
+
+        let mut text = String::new();
+        for (i, chunk) in chunks.iter().rev().enumerate() {
+            if i == 0 {
+                text += &chunk.to_string();
+            } else {
+                text += &format!("{chunk:09}");
+            }
+        }
+
+        f.pad_integral(true, "", &text)
+    }
+}
@@ -0,0 +1,284 @@
+use functions::fibonacci::{self, Method, Overflow, Word};
+use std::fmt::Display;
+use std::hint::black_box;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use std::{env, process};
+
+const DEFAULT_RUNS: u32 = 1_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Type {
+    U32,
+    U64,
+    U128,
+    Big,
+}
+
+impl Type {
+    fn parse(name: &str) -> Option<Type> {
+        match name {
+            "u32" => Some(Type::U32),
+            "u64" => Some(Type::U64),
+            "u128" => Some(Type::U128),
+            "big" => Some(Type::Big),
+            _ => None,
+        }
+    }
+}
+
+enum Command {
+    Nth(u64),
+    /// Every Fibonacci number that fits in the type.
+    List,
+    Bench(u32),
+}
+
+/// `--method` and `--type` are kept as given, as `bench` runs every
+/// method and type unless told otherwise.
+struct Config {
+    command: Command,
+    method: Option<Method>,
+    number_type: Option<Type>,
+}
+
+impl Config {
+    fn build(mut args: impl Iterator<Item = String>) -> Result<Config, String> {
+        args.next();
+
+        let mut n = None;
+        let mut list = false;
+        let mut bench = false;
+        let mut runs = DEFAULT_RUNS;
+        let mut method = None;
+        let mut number_type = None;
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("missing value for {arg}"))
+            };
+
+            match arg.as_str() {
+                "bench" => bench = true,
+                "--list" => list = true,
+                "--runs" => runs = parse_number(&arg, &value()?)?,
+                "--method" => {
+                    let name = value()?;
+                    method = Some(Method::parse(&name).ok_or(format!("unknown method: {name}"))?);
+                }
+                "--type" => {
+                    let name = value()?;
+                    number_type = Some(Type::parse(&name).ok_or(format!("unknown type: {name}"))?);
+                }
+                _ if arg.starts_with("--") => return Err(format!("unknown argument: {arg}")),
+                _ => n = Some(parse_number("n", &arg)?),
+            }
+        }
+
+        let command = match (n, list, bench) {
+            (Some(n), false, false) => Command::Nth(n),
+            (None, true, false) => Command::List,
+            (None, false, true) => Command::Bench(runs.max(1)),
+            _ => return Err(String::from("give exactly one of N, --list or bench")),
+        };
+        if number_type == Some(Type::Big) && method == Some(Method::Memoized) {
+            return Err(String::from("--method memoized needs a fixed-size --type"));
+        }
+        if number_type == Some(Type::Big) && matches!(command, Command::List) {
+            return Err(String::from("--list needs a fixed-size --type"));
+        }
+
+        Ok(Config {
+            command,
+            method,
+            number_type,
+        })
+    }
+
+    fn method(&self) -> Method {
+        self.method.unwrap_or(Method::FastDoubling)
+    }
+
+    fn number_type(&self) -> Type {
+        self.number_type.unwrap_or(Type::U64)
+    }
+}
+
+fn parse_number<T: FromStr>(arg: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{arg} expects a non-negative number, got {value:?}"))
+}
+
+fn main() {
+    let config = Config::build(env::args()).unwrap_or_else(|err| {
+        eprintln!("Problem parsing arguments: {err}");
+        eprintln!("Usage: fib N [--method iterative|memoized|doubling] [--type u32|u64|u128|big]");
+        eprintln!("       fib --list [--type u32|u64|u128]");
+        eprintln!("       fib bench [--runs N] [--method M] [--type T]");
+        process::exit(2);
+    });
+
+    match config.command {
+        Command::Nth(n) => print_nth(n, &config),
+        Command::List => match config.number_type() {
+            Type::U32 => list::<u32>(),
+            Type::U64 => list::<u64>(),
+            Type::U128 | Type::Big => list::<u128>(),
+        },
+        Command::Bench(runs) => bench(runs, &config),
+    }
+}
+
+fn print_nth(n: u64, config: &Config) {
+    let method = config.method();
+    let result = match config.number_type() {
+        Type::U32 => show(fibonacci::nth::<u32>(n, method)),
+        Type::U64 => show(fibonacci::nth::<u64>(n, method)),
+        Type::U128 => show(fibonacci::nth::<u128>(n, method)),
+        Type::Big => Ok(match method {
+            Method::Iterative => fibonacci::big_iterative(n).to_string(),
+            _ => fibonacci::big_fast_doubling(n).to_string(),
+        }),
+    };
+
+    match result {
+        Ok(value) => println!("F({n}) = {value}"),
+        Err(overflow) => {
+            eprintln!("{overflow}; try a wider --type, or --type big");
+            process::exit(1);
+        }
+    }
+}
+
+fn show<T: Display>(result: Result<T, Overflow>) -> Result<String, Overflow> {
+    result.map(|value| value.to_string())
+}
+
+fn list<T: Word>() {
+    for (n, value) in fibonacci::sequence::<T>().enumerate() {
+        println!("F({n}) = {value}");
+    }
+}
+
+/// Average time per call of `f`, after one untimed warm-up call.
+fn time<R>(runs: u32, mut f: impl FnMut() -> R) -> Duration {
+    black_box(f());
+
+    let started = Instant::now();
+    for _ in 0..runs {
+        black_box(f());
+    }
+    started.elapsed() / runs
+}
+
+/// Each method at the largest n its type allows, then `big` at sizes
+/// where only the algorithm matters, or just the `--method` and `--type`
+/// asked for. Memoized calls start from an empty memo every time, or they
+/// would only measure a lookup.
+fn bench(runs: u32, config: &Config) {
+    let methods = match config.method {
+        Some(method) => vec![method],
+        None => Method::ALL.to_vec(),
+    };
+    let types = match config.number_type {
+        Some(number_type) => vec![number_type],
+        None => vec![Type::U64, Type::U128, Type::Big],
+    };
+
+    println!("Method      Type       n      Runs    Per call");
+    for number_type in types {
+        match number_type {
+            Type::U32 => bench_word::<u32>(runs, &methods),
+            Type::U64 => bench_word::<u64>(runs, &methods),
+            Type::U128 => bench_word::<u128>(runs, &methods),
+            Type::Big => bench_big(runs, &methods),
+        }
+    }
+}
+
+/// The largest n whose F(n) fits in `T`.
+fn largest_n<T: Word>() -> u64 {
+    fibonacci::sequence::<T>().count() as u64 - 1
+}
+
+fn bench_word<T: Word>(runs: u32, methods: &[Method]) {
+    let n = largest_n::<T>();
+
+    for &method in methods {
+        let per_call = time(runs, || fibonacci::nth::<T>(black_box(n), method));
+        println!(
+            "{:<10}  {:<6} {n:>7} {runs:>9} {per_call:>11.2?}",
+            method.name(),
+            T::NAME
+        );
+    }
+}
+
+/// There is no memoized `big`, so that method is left out here.
+fn bench_big(runs: u32, methods: &[Method]) {
+    // Bignum calls are far slower; fewer runs keep the whole bench short.
+    let big_runs = (runs / 100).clamp(1, 10);
+
+    for n in [1_000, 10_000, 30_000] {
+        for &method in methods {
+            let per_call = match method {
+                Method::Iterative => time(big_runs, || fibonacci::big_iterative(black_box(n))),
+                Method::FastDoubling => {
+                    time(big_runs, || fibonacci::big_fast_doubling(black_box(n)))
+                }
+                Method::Memoized => continue,
+            };
+            println!(
+                "{:<10}  big    {n:>7} {big_runs:>9} {per_call:>11.2?}",
+                method.name()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(args: &[&str]) -> Result<Config, String> {
+        let args = ["fib"].iter().chain(args).map(|arg| arg.to_string());
+        Config::build(args)
+    }
+
+    #[test]
+    fn bench_keeps_the_method_and_type_it_was_given() {
+        let config = build(&["bench", "--method", "iterative", "--type", "u32"]).unwrap();
+        assert!(matches!(config.command, Command::Bench(DEFAULT_RUNS)));
+        assert_eq!(config.method, Some(Method::Iterative));
+        assert_eq!(config.number_type, Some(Type::U32));
+
+        let config = build(&["bench", "--runs", "5"]).unwrap();
+        assert!(matches!(config.command, Command::Bench(5)));
+        assert_eq!((config.method, config.number_type), (None, None));
+    }
+
+    #[test]
+    fn nth_defaults_to_doubling_in_u64() {
+        let config = build(&["10"]).unwrap();
+        assert_eq!(config.method(), Method::FastDoubling);
+        assert_eq!(config.number_type(), Type::U64);
+    }
+
+    #[test]
+    fn rejects_memoized_big_numbers_everywhere() {
+        for command in ["10", "bench"] {
+            let args = [command, "--method", "memoized", "--type", "big"];
+            assert!(build(&args).is_err(), "{command}");
+        }
+        assert!(build(&["--list", "--type", "big"]).is_err());
+    }
+
+    #[test]
+    fn benches_each_type_at_its_largest_n() {
+        assert_eq!(largest_n::<u32>(), 47);
+        assert_eq!(largest_n::<u64>(), 93);
+        assert_eq!(largest_n::<u128>(), 186);
+    }
+}
@@ -0,0 +1,167 @@
+use crate::temperature::{Scale, Temperature};
+use std::str::FromStr;
+
+const DEFAULT_PRECISION: usize = 2;
+const DEFAULT_STEP: f64 = 10.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    /// Print the chapter's listing, `plus_one(5)`, which is what running
+    /// with no arguments does.
+    Listing,
+    /// Convert each value, to `--to` or to every other scale.
+    Convert(Vec<Temperature>),
+    /// One row every `step` degrees from `start` to `end`.
+    Table {
+        start: Temperature,
+        end: Temperature,
+        step: f64,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    pub command: Command,
+    pub to: Option<Scale>,
+    /// Digits after the decimal point.
+    pub precision: usize,
+}
+
+impl Config {
+    /// Parses the full argument list, including the program name.
+    ///
+    /// Anything that does not start with `--` is a temperature, so negative
+    /// values like `-40C` need no quoting.
+    pub fn build(mut args: impl Iterator<Item = String>) -> Result<Config, String> {
+        args.next();
+        let mut args = args.peekable();
+        if args.peek().is_none() {
+            return Ok(Config {
+                command: Command::Listing,
+                to: None,
+                precision: DEFAULT_PRECISION,
+            });
+        }
+
+        let mut values = Vec::new();
+        let mut table = false;
+        let mut step = None;
+        let mut to = None;
+        let mut precision = DEFAULT_PRECISION;
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("missing value for {arg}"))
+            };
+
+            match arg.as_str() {
+                "--table" => table = true,
+                "--step" => step = Some(parse_number(&arg, &value()?)?),
+                "--to" => {
+                    let name = value()?;
+                    to = Some(Scale::parse(&name).ok_or(format!("unknown scale: {name}"))?);
+                }
+                "--precision" => precision = parse_number(&arg, &value()?)?,
+                _ if arg.starts_with("--") => return Err(format!("unknown argument: {arg}")),
+                _ => values.push(arg.parse::<Temperature>().map_err(|err| err.to_string())?),
+            }
+        }
+
+        let command = if table {
+            match values[..] {
+                [start, end] => Command::Table {
+                    start,
+                    end,
+                    step: step.unwrap_or(DEFAULT_STEP),
+                },
+                _ => return Err(String::from("--table expects a start and an end")),
+            }
+        } else {
+            if step.is_some() {
+                return Err(String::from("--step only works with --table"));
+            }
+            if values.is_empty() {
+                return Err(String::from("no temperature given"));
+            }
+            Command::Convert(values)
+        };
+
+        Ok(Config {
+            command,
+            to,
+            precision,
+        })
+    }
+
+    /// `--to`, or every scale other than `from`'s.
+    pub fn targets(&self, from: Scale) -> Vec<Scale> {
+        match self.to {
+            Some(to) => vec![to],
+            None => Scale::ALL
+                .into_iter()
+                .filter(|&scale| scale != from)
+                .collect(),
+        }
+    }
+}
+
+fn parse_number<T: FromStr>(arg: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{arg} expects a number, got {value:?}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(args: &[&str]) -> Result<Config, String> {
+        let args = ["functions"].iter().chain(args).map(|arg| arg.to_string());
+        Config::build(args)
+    }
+
+    #[test]
+    fn runs_the_listing_without_arguments() {
+        assert_eq!(build(&[]).unwrap().command, Command::Listing);
+        assert_eq!(
+            build(&["--to", "F"]),
+            Err(String::from("no temperature given"))
+        );
+    }
+
+    #[test]
+    fn builds_conversions_and_tables() {
+        let config = build(&["-40C", "98.6F", "--to", "k", "--precision", "1"]).unwrap();
+        assert_eq!(
+            config.command,
+            Command::Convert(vec![
+                Temperature::celsius(-40.0).unwrap(),
+                Temperature::fahrenheit(98.6).unwrap(),
+            ])
+        );
+        assert_eq!((config.to, config.precision), (Some(Scale::Kelvin), 1));
+
+        let config = build(&["--table", "0C", "100C"]).unwrap();
+        assert_eq!(
+            config.command,
+            Command::Table {
+                start: Temperature::celsius(0.0).unwrap(),
+                end: Temperature::celsius(100.0).unwrap(),
+                step: DEFAULT_STEP,
+            }
+        );
+        assert_eq!(
+            config.targets(Scale::Celsius),
+            [Scale::Fahrenheit, Scale::Kelvin]
+        );
+    }
+
+    #[test]
+    fn rejects_bad_combinations() {
+        assert!(build(&["--table", "0C"]).is_err());
+        assert!(build(&["0C", "--step", "5"]).is_err());
+        assert!(build(&["0C", "--to", "R"]).is_err());
+        assert!(build(&["0C", "--precision"]).is_err());
+    }
+}
@@ -0,0 +1,332 @@
+use crate::big::BigUint;
+use std::error::Error;
+use std::fmt;
+
+/// The unsigned types Fibonacci numbers can be computed in, with every
+/// step checked instead of wrapping or panicking.
+pub trait Word: Copy + fmt::Debug + fmt::Display {
+    const NAME: &'static str;
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! word {
+    ($($t:ty),*) => {$(
+        impl Word for $t {
+            const NAME: &'static str = stringify!($t);
+            const ZERO: $t = 0;
+            const ONE: $t = 1;
+
+            fn checked_add(self, other: $t) -> Option<$t> {
+                <$t>::checked_add(self, other)
+            }
+
+            fn checked_sub(self, other: $t) -> Option<$t> {
+                <$t>::checked_sub(self, other)
+            }
+
+            fn checked_mul(self, other: $t) -> Option<$t> {
+                <$t>::checked_mul(self, other)
+            }
+        }
+    )*};
+}
+
+word!(u32, u64, u128);
+
+/// F(n) is too big for the type it was asked for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow {
+    pub n: u64,
+    pub type_name: &'static str,
+}
+
+impl Overflow {
+    fn of<T: Word>(n: u64) -> Overflow {
+        Overflow {
+            n,
+            type_name: T::NAME,
+        }
+    }
+}
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "F({}) does not fit in {}", self.n, self.type_name)
+    }
+}
+
+impl Error for Overflow {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Iterative,
+    Memoized,
+    FastDoubling,
+}
+
+impl Method {
+    pub const ALL: [Method; 3] = [Method::Iterative, Method::Memoized, Method::FastDoubling];
+
+    pub fn parse(name: &str) -> Option<Method> {
+        match name {
+            "iterative" => Some(Method::Iterative),
+            "memoized" => Some(Method::Memoized),
+            "doubling" | "fast-doubling" => Some(Method::FastDoubling),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Method::Iterative => "iterative",
+            Method::Memoized => "memoized",
+            Method::FastDoubling => "doubling",
+        }
+    }
+}
+
+/// F(n) with the chosen method; they all agree, only the speed differs.
+pub fn nth<T: Word>(n: u64, method: Method) -> Result<T, Overflow> {
+    match method {
+        Method::Iterative => iterative(n),
+        Method::Memoized => Memo::new().get(n),
+        Method::FastDoubling => fast_doubling(n),
+    }
+}
+
+/// Walks up from F(0): n checked additions.
+pub fn iterative<T: Word>(n: u64) -> Result<T, Overflow> {
+    // Starting from F(-1) = 1 means the loop never computes past F(n), so
+    // it only fails when F(n) itself does not fit.
+    let (mut previous, mut current) = (T::ONE, T::ZERO);
+
+    for _ in 0..n {
+        let next = previous.checked_add(current).ok_or(Overflow::of::<T>(n))?;
+        previous = current;
+        current = next;
+    }
+
+    Ok(current)
+}
+
+/// Remembers every number computed so far, so asking again is a lookup
+/// and asking for a larger n only computes the missing part.
+#[derive(Debug, Clone)]
+pub struct Memo<T> {
+    known: Vec<T>,
+}
+
+impl<T: Word> Memo<T> {
+    pub fn new() -> Memo<T> {
+        Memo {
+            known: vec![T::ZERO, T::ONE],
+        }
+    }
+
+    pub fn get(&mut self, n: u64) -> Result<T, Overflow> {
+        let index = usize::try_from(n).map_err(|_| Overflow::of::<T>(n))?;
+
+        while self.known.len() <= index {
+            let len = self.known.len();
+            let next = self.known[len - 2]
+                .checked_add(self.known[len - 1])
+                .ok_or(Overflow::of::<T>(n))?;
+            self.known.push(next);
+        }
+
+        Ok(self.known[index])
+    }
+}
+
+impl<T: Word> Default for Memo<T> {
+    fn default() -> Memo<T> {
+        Memo::new()
+    }
+}
+
+/// F(2k) = F(k) * (2 * F(k + 1) - F(k)) and F(2k + 1) = F(k)² + F(k + 1)²,
+/// so F(n) takes about log₂(n) steps instead of n.
+pub fn fast_doubling<T: Word>(n: u64) -> Result<T, Overflow> {
+    let overflow = || Overflow::of::<T>(n);
+
+    // Only F(n) is computed at the last step, not the pair, so a value
+    // whose successor overflows still comes back.
+    let (a, b) = doubling_pair::<T>(n / 2).ok_or_else(overflow)?;
+    let value = if n.is_multiple_of(2) {
+        b.checked_add(b)
+            .and_then(|twice| twice.checked_sub(a))
+            .and_then(|difference| a.checked_mul(difference))
+    } else {
+        a.checked_mul(a)
+            .zip(b.checked_mul(b))
+            .and_then(|(a2, b2)| a2.checked_add(b2))
+    };
+
+    value.ok_or_else(overflow)
+}
+
+/// (F(k), F(k + 1)), working down the bits of `k` from the top.
+fn doubling_pair<T: Word>(k: u64) -> Option<(T, T)> {
+    let mut pair = (T::ZERO, T::ONE);
+
+    for bit in (0..u64::BITS - k.leading_zeros()).rev() {
+        let (a, b) = pair;
+        let even = a.checked_mul(b.checked_add(b)?.checked_sub(a)?)?;
+        let odd = a.checked_mul(a)?.checked_add(b.checked_mul(b)?)?;
+
+        pair = if (k >> bit) & 1 == 0 {
+            (even, odd)
+        } else {
+            (odd, even.checked_add(odd)?)
+        };
+    }
+
+    Some(pair)
+}
+
+/// F(0), F(1), F(2), ... for as long as they fit in `T`.
+pub fn sequence<T: Word>() -> Sequence<T> {
+    Sequence {
+        previous: T::ONE,
+        current: Some(T::ZERO),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Sequence<T> {
+    previous: T,
+    current: Option<T>,
+}
+
+impl<T: Word> Iterator for Sequence<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let current = self.current?;
+
+        self.current = self.previous.checked_add(current);
+        self.previous = current;
+
+        Some(current)
+    }
+}
+
+/// F(n) of any size with n additions.
+pub fn big_iterative(n: u64) -> BigUint {
+    let (mut previous, mut current) = (BigUint::one(), BigUint::zero());
+
+    for _ in 0..n {
+        let next = &previous + &current;
+        previous = current;
+        current = next;
+    }
+
+    current
+}
+
+/// F(n) of any size by fast doubling; the way to go for very large n.
+pub fn big_fast_doubling(n: u64) -> BigUint {
+    let (mut a, mut b) = (BigUint::zero(), BigUint::one());
+
+    for bit in (0..u64::BITS - n.leading_zeros()).rev() {
+        let twice_b = &b + &b;
+        let even = &a * &(&twice_b - &a);
+        let odd = &(&a * &a) + &(&b * &b);
+
+        (a, b) = if (n >> bit) & 1 == 0 {
+            (even, odd)
+        } else {
+            let next = &even + &odd;
+            (odd, next)
+        };
+    }
+
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks that every method computes F(`last`) in `T` and overflows
+    /// on F(`last` + 1).
+    fn assert_boundary<T: Word>(last: u64) {
+        let expected = big_iterative(last).to_string();
+        let past = Err(Overflow {
+            n: last + 1,
+            type_name: T::NAME,
+        });
+
+        for method in Method::ALL {
+            let value = nth::<T>(last, method).map(|value| value.to_string());
+            assert_eq!(value, Ok(expected.clone()), "{method:?} {}", T::NAME);
+            let value = nth::<T>(last + 1, method).map(|value| value.to_string());
+            assert_eq!(value, past, "{method:?} {}", T::NAME);
+        }
+    }
+
+    #[test]
+    fn u64_holds_up_to_f93() {
+        assert_boundary::<u64>(93);
+        assert_eq!(
+            nth::<u64>(93, Method::Iterative),
+            Ok(12_200_160_415_121_876_738)
+        );
+        assert_eq!(
+            nth::<u64>(94, Method::Memoized).unwrap_err().to_string(),
+            "F(94) does not fit in u64"
+        );
+    }
+
+    #[test]
+    fn u128_holds_up_to_f186() {
+        assert_boundary::<u128>(186);
+        assert_eq!(
+            nth::<u128>(186, Method::FastDoubling),
+            Ok(332_825_110_087_067_562_321_196_029_789_634_457_848)
+        );
+    }
+
+    #[test]
+    fn u32_holds_up_to_f47() {
+        assert_boundary::<u32>(47);
+    }
+
+    #[test]
+    fn methods_agree_on_small_numbers() {
+        let expected: Vec<u64> = sequence::<u64>().take(50).collect();
+        for method in Method::ALL {
+            let values: Vec<u64> = (0..50).map(|n| nth(n, method).unwrap()).collect();
+            assert_eq!(values, expected, "{method:?}");
+        }
+        assert_eq!(&expected[..8], [0, 1, 1, 2, 3, 5, 8, 13]);
+    }
+
+    #[test]
+    fn sequence_stops_at_the_last_value_that_fits() {
+        assert_eq!(sequence::<u64>().count(), 94);
+        assert_eq!(sequence::<u128>().last(), nth(186, Method::Iterative).ok());
+    }
+
+    #[test]
+    fn memo_extends_what_it_already_knows() {
+        let mut memo = Memo::<u64>::new();
+        assert_eq!(memo.get(10), Ok(55));
+        assert_eq!(memo.get(93), nth(93, Method::FastDoubling));
+        assert!(memo.get(94).is_err());
+        assert_eq!(memo.get(20), Ok(6_765));
+    }
+
+    #[test]
+    fn big_methods_agree_past_every_fixed_size() {
+        for n in [0, 1, 2, 187, 500, 1_001] {
+            assert_eq!(big_iterative(n), big_fast_doubling(n), "{n}");
+        }
+        assert_eq!(big_fast_doubling(100).to_string(), "354224848179261915075");
+    }
+}
@@ -0,0 +1,21 @@
+pub mod big;
+pub mod config;
+pub mod fibonacci;
+pub mod temperature;
+
+pub use crate::big::BigUint;
+pub use crate::config::{Command, Config};
+pub use crate::temperature::{Scale, Temperature, TemperatureError};
+
+/// The chapter's first function with a return value.
+///
+/// Like any `+`, this panics on overflow in debug builds and wraps around
+/// in release builds; see `checked_plus_one` for the careful version.
+pub fn plus_one(x: i32) -> i32 {
+    x + 1
+}
+
+/// `None` instead of overflowing when `x` is `i32::MAX`.
+pub fn checked_plus_one(x: i32) -> Option<i32> {
+    x.checked_add(1)
+}
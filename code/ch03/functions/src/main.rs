@@ -1,9 +1,71 @@
+use functions::temperature;
+use functions::{plus_one, Command, Config, Temperature};
+use std::{env, process};
+
 fn main() {
-    let x = plus_one(5);
+    let config = Config::build(env::args()).unwrap_or_else(|err| {
+        eprintln!("Problem parsing arguments: {err}");
+        eprintln!("Usage: functions");
+        eprintln!("       functions TEMP... [--to C|F|K] [--precision N]");
+        eprintln!("       functions --table START END [--step N] [--to C|F|K] [--precision N]");
+        eprintln!("Without arguments, runs the chapter's listing.");
+        eprintln!("Temperatures look like 98.6F, -40C or 300K.");
+        process::exit(2);
+    });
+
+    match &config.command {
+        Command::Listing => {
+            let x = plus_one(5);
 
-    println!("The value of x is: {x}");
+            println!("The value of x is: {x}");
+        }
+        Command::Convert(values) => {
+            for &value in values {
+                println!("{}", convert(value, &config));
+            }
+        }
+        Command::Table { start, end, step } => {
+            let rows = temperature::table(*start, *end, *step).unwrap_or_else(|err| {
+                eprintln!("Problem building the table: {err}");
+                process::exit(1);
+            });
+            print_table(&rows, &config);
+        }
+    }
 }
 
-fn plus_one(x: i32) -> i32 {
-    x + 1
-}
\ No newline at end of file
+/// `98.60°F = 37.00°C = 310.15K`
+fn convert(value: Temperature, config: &Config) -> String {
+    let precision = config.precision;
+    let mut line = format!("{value:.precision$}");
+
+    for scale in config.targets(value.scale()) {
+        line += &format!(" = {:.precision$}", value.to(scale));
+    }
+
+    line
+}
+
+fn print_table(rows: &[Temperature], config: &Config) {
+    let from = match rows.first() {
+        Some(first) => first.scale(),
+        None => return,
+    };
+    let targets = config.targets(from);
+    let precision = config.precision;
+    let width = precision + 10;
+
+    let mut header = format!("{:>width$}", from.symbol());
+    for scale in &targets {
+        header += &format!(" {:>width$}", scale.symbol());
+    }
+    println!("{header}");
+
+    for &row in rows {
+        let mut line = format!("{:>width$.precision$}", row.value());
+        for &scale in &targets {
+            line += &format!(" {:>width$.precision$}", row.to(scale).value());
+        }
+        println!("{line}");
+    }
+}
@@ -0,0 +1,437 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Tables longer than this are almost certainly a typo in the step.
+pub const MAX_TABLE_ROWS: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Scale {
+    Celsius,
+    Fahrenheit,
+    Kelvin,
+}
+
+impl Scale {
+    pub const ALL: [Scale; 3] = [Scale::Celsius, Scale::Fahrenheit, Scale::Kelvin];
+
+    /// Accepts the symbol with or without a degree sign, or the full name.
+    pub fn parse(name: &str) -> Option<Scale> {
+        let name = name.trim().trim_start_matches('°').to_lowercase();
+
+        match name.as_str() {
+            "c" | "celsius" => Some(Scale::Celsius),
+            "f" | "fahrenheit" => Some(Scale::Fahrenheit),
+            "k" | "kelvin" => Some(Scale::Kelvin),
+            _ => None,
+        }
+    }
+
+    /// Kelvin has no degree sign.
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Scale::Celsius => "°C",
+            Scale::Fahrenheit => "°F",
+            Scale::Kelvin => "K",
+        }
+    }
+
+    pub fn absolute_zero(self) -> f64 {
+        match self {
+            Scale::Celsius => -273.15,
+            Scale::Fahrenheit => -459.67,
+            Scale::Kelvin => 0.0,
+        }
+    }
+
+    fn to_kelvin(self, value: f64) -> f64 {
+        match self {
+            Scale::Celsius => value + 273.15,
+            Scale::Fahrenheit => (value + 459.67) * 5.0 / 9.0,
+            Scale::Kelvin => value,
+        }
+    }
+
+    fn convert_kelvin(self, kelvin: f64) -> f64 {
+        match self {
+            Scale::Celsius => kelvin - 273.15,
+            Scale::Fahrenheit => kelvin * 9.0 / 5.0 - 459.67,
+            Scale::Kelvin => kelvin,
+        }
+    }
+}
+
+impl fmt::Display for Scale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Scale::Celsius => "Celsius",
+            Scale::Fahrenheit => "Fahrenheit",
+            Scale::Kelvin => "Kelvin",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemperatureError {
+    Empty,
+    /// A number with nothing saying which scale it is in.
+    MissingScale(String),
+    UnknownScale(String),
+    InvalidNumber(String),
+    NotFinite,
+    BelowAbsoluteZero {
+        value: f64,
+        scale: Scale,
+    },
+    InvalidStep(f64),
+    TooManyRows(usize),
+}
+
+impl fmt::Display for TemperatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemperatureError::Empty => write!(f, "no temperature given"),
+            TemperatureError::MissingScale(input) => {
+                write!(f, "{input:?} needs a scale, such as {input}C or {input}F")
+            }
+            TemperatureError::UnknownScale(scale) => {
+                write!(f, "unknown scale {scale:?}; use C, F or K")
+            }
+            TemperatureError::InvalidNumber(number) => write!(f, "{number:?} is not a number"),
+            TemperatureError::NotFinite => write!(f, "temperatures must be finite"),
+            TemperatureError::BelowAbsoluteZero { value, scale } => write!(
+                f,
+                "{value}{} is below absolute zero ({}{})",
+                scale.symbol(),
+                scale.absolute_zero(),
+                scale.symbol()
+            ),
+            TemperatureError::InvalidStep(step) => {
+                write!(f, "the step must be a positive number, got {step}")
+            }
+            TemperatureError::TooManyRows(rows) => write!(
+                f,
+                "that table would have {rows} rows; the limit is {MAX_TABLE_ROWS}"
+            ),
+        }
+    }
+}
+
+impl Error for TemperatureError {}
+
+/// A temperature that is never below absolute zero.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Temperature {
+    value: f64,
+    scale: Scale,
+}
+
+impl Temperature {
+    pub fn new(value: f64, scale: Scale) -> Result<Temperature, TemperatureError> {
+        if !value.is_finite() {
+            return Err(TemperatureError::NotFinite);
+        }
+        if value < scale.absolute_zero() {
+            return Err(TemperatureError::BelowAbsoluteZero { value, scale });
+        }
+
+        Ok(Temperature { value, scale })
+    }
+
+    pub fn celsius(value: f64) -> Result<Temperature, TemperatureError> {
+        Temperature::new(value, Scale::Celsius)
+    }
+
+    pub fn fahrenheit(value: f64) -> Result<Temperature, TemperatureError> {
+        Temperature::new(value, Scale::Fahrenheit)
+    }
+
+    pub fn kelvin(value: f64) -> Result<Temperature, TemperatureError> {
+        Temperature::new(value, Scale::Kelvin)
+    }
+
+    pub fn value(self) -> f64 {
+        self.value
+    }
+
+    pub fn scale(self) -> Scale {
+        self.scale
+    }
+
+    /// The same temperature on another scale.
+    ///
+    /// Rounding can land a hair below absolute zero, so the result is
+    /// clamped to it rather than rejected.
+    pub fn to(self, scale: Scale) -> Temperature {
+        if scale == self.scale {
+            return self;
+        }
+
+        let value = scale.convert_kelvin(self.scale.to_kelvin(self.value));
+        Temperature {
+            value: value.max(scale.absolute_zero()),
+            scale,
+        }
+    }
+}
+
+/// Uses the formatter's precision, so `{:.1}` prints `37.0°C`.
+impl fmt::Display for Temperature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match f.precision() {
+            Some(precision) => write!(f, "{:.*}{}", precision, self.value, self.scale.symbol()),
+            None => write!(f, "{}{}", self.value, self.scale.symbol()),
+        }
+    }
+}
+
+/// Parses `98.6F`, `-40 °C`, `300K` or `20 celsius`.
+impl FromStr for Temperature {
+    type Err = TemperatureError;
+
+    fn from_str(input: &str) -> Result<Temperature, TemperatureError> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(TemperatureError::Empty);
+        }
+
+        // The scale is the trailing run of letters; stopping at the last
+        // digit keeps exponents like `1e3F` inside the number.
+        let number =
+            input.trim_end_matches(|c: char| c.is_alphabetic() || c == '°' || c.is_whitespace());
+        let scale = input[number.len()..].trim();
+        let number = number.trim();
+
+        if number.is_empty() {
+            return Err(TemperatureError::InvalidNumber(input.to_string()));
+        }
+        if scale.is_empty() {
+            return Err(TemperatureError::MissingScale(input.to_string()));
+        }
+
+        let scale =
+            Scale::parse(scale).ok_or_else(|| TemperatureError::UnknownScale(scale.to_string()))?;
+        let value = number
+            .parse()
+            .map_err(|_| TemperatureError::InvalidNumber(number.to_string()))?;
+
+        Temperature::new(value, scale)
+    }
+}
+
+/// Every `step` degrees from `start` to `end`, on `start`'s scale.
+///
+/// `end` is converted to that scale first and is included when a step lands
+/// on it. The table counts down when `end` is colder than `start`.
+pub fn table(
+    start: Temperature,
+    end: Temperature,
+    step: f64,
+) -> Result<Vec<Temperature>, TemperatureError> {
+    if !step.is_finite() || step <= 0.0 {
+        return Err(TemperatureError::InvalidStep(step));
+    }
+
+    let scale = start.scale();
+    let (from, to) = (start.value(), end.to(scale).value());
+    let direction = if to < from { -1.0 } else { 1.0 };
+
+    // Counting steps, instead of adding `step` repeatedly, keeps rounding
+    // errors from piling up; the small slack absorbs the ones left, and
+    // clamping keeps a last row at absolute zero from dipping below it.
+    let steps = ((to - from).abs() / step + 1e-9).floor();
+    if steps >= MAX_TABLE_ROWS as f64 {
+        return Err(TemperatureError::TooManyRows(steps as usize + 1));
+    }
+
+    let rows = (0..=steps as usize)
+        .map(|i| Temperature {
+            value: (from + direction * step * i as f64).max(scale.absolute_zero()),
+            scale,
+        })
+        .collect();
+
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> Result<Temperature, TemperatureError> {
+        input.parse()
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+    }
+
+    fn values(rows: &[Temperature]) -> Vec<f64> {
+        rows.iter().map(|row| row.value()).collect()
+    }
+
+    #[test]
+    fn parses_numbers_with_any_scale() {
+        assert_eq!(parse("98.6F"), Temperature::fahrenheit(98.6));
+        assert_eq!(parse("-40c"), Temperature::celsius(-40.0));
+        assert_eq!(parse("0K"), Temperature::kelvin(0.0));
+        assert_eq!(parse(" -40 °C "), Temperature::celsius(-40.0));
+        assert_eq!(parse("20 celsius"), Temperature::celsius(20.0));
+        assert_eq!(parse("300 Kelvin"), Temperature::kelvin(300.0));
+        assert_eq!(parse("1e3F"), Temperature::fahrenheit(1_000.0));
+    }
+
+    #[test]
+    fn rejects_bad_scales_and_numbers() {
+        assert_eq!(parse("  "), Err(TemperatureError::Empty));
+        assert_eq!(
+            parse("20"),
+            Err(TemperatureError::MissingScale(String::from("20")))
+        );
+        assert_eq!(
+            parse("20X"),
+            Err(TemperatureError::UnknownScale(String::from("X")))
+        );
+        assert_eq!(
+            parse("20 degrees"),
+            Err(TemperatureError::UnknownScale(String::from("degrees")))
+        );
+        assert_eq!(
+            parse("F"),
+            Err(TemperatureError::InvalidNumber(String::from("F")))
+        );
+        assert_eq!(
+            parse("1.2.3C"),
+            Err(TemperatureError::InvalidNumber(String::from("1.2.3")))
+        );
+        // `NaN` and `inf` are all letters, so they are read as the scale.
+        assert!(matches!(
+            parse("NaNC"),
+            Err(TemperatureError::InvalidNumber(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_temperatures_below_absolute_zero() {
+        for scale in Scale::ALL {
+            let zero = scale.absolute_zero();
+            assert!(Temperature::new(zero, scale).is_ok(), "{scale}");
+
+            let below = zero - 0.01;
+            assert_eq!(
+                Temperature::new(below, scale),
+                Err(TemperatureError::BelowAbsoluteZero {
+                    value: below,
+                    scale
+                })
+            );
+        }
+
+        assert_eq!(
+            parse("-300C").unwrap_err().to_string(),
+            "-300°C is below absolute zero (-273.15°C)"
+        );
+        assert_eq!(
+            Temperature::celsius(f64::INFINITY),
+            Err(TemperatureError::NotFinite)
+        );
+    }
+
+    #[test]
+    fn converts_known_points() {
+        let boiling = Temperature::celsius(100.0).unwrap();
+        assert_close(boiling.to(Scale::Fahrenheit).value(), 212.0);
+        assert_close(boiling.to(Scale::Kelvin).value(), 373.15);
+
+        let same = Temperature::fahrenheit(-40.0).unwrap().to(Scale::Celsius);
+        assert_close(same.value(), -40.0);
+
+        let zero = Temperature::kelvin(0.0).unwrap();
+        assert_eq!(zero.to(Scale::Celsius).value(), -273.15);
+        assert_eq!(zero.to(Scale::Fahrenheit).value(), -459.67);
+    }
+
+    #[test]
+    fn conversions_survive_a_round_trip() {
+        for from in Scale::ALL {
+            for to in Scale::ALL {
+                for value in [0.0, 36.6, 1_000.0, from.absolute_zero()] {
+                    let start = Temperature::new(value, from).unwrap();
+                    let back = start.to(to).to(from);
+
+                    assert_eq!(back.scale(), from);
+                    assert_close(back.value(), value);
+                    assert!(start.to(to).value() >= to.absolute_zero());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn displays_with_the_requested_precision() {
+        let body = Temperature::celsius(37.0).unwrap();
+        assert_eq!(format!("{body:.1}"), "37.0°C");
+        assert_eq!(body.to(Scale::Kelvin).to_string(), "310.15K");
+    }
+
+    #[test]
+    fn tables_count_up_or_down_and_include_the_end() {
+        let freezing = Temperature::celsius(0.0).unwrap();
+        let boiling = Temperature::celsius(100.0).unwrap();
+
+        let up = table(freezing, boiling, 25.0).unwrap();
+        assert_eq!(values(&up), [0.0, 25.0, 50.0, 75.0, 100.0]);
+
+        let down = table(boiling, freezing, 50.0).unwrap();
+        assert_eq!(values(&down), [100.0, 50.0, 0.0]);
+
+        let short = table(freezing, Temperature::celsius(10.0).unwrap(), 3.0).unwrap();
+        assert_eq!(values(&short), [0.0, 3.0, 6.0, 9.0]);
+
+        let single = table(freezing, freezing, 1.0).unwrap();
+        assert_eq!(values(&single), [0.0]);
+    }
+
+    #[test]
+    fn tables_stay_on_the_starting_scale() {
+        let start = Temperature::celsius(0.0).unwrap();
+        let end = Temperature::fahrenheit(212.0).unwrap();
+
+        let rows = table(start, end, 50.0).unwrap();
+        assert!(rows.iter().all(|row| row.scale() == Scale::Celsius));
+        assert_eq!(rows.len(), 3);
+        assert_close(rows[2].value(), 100.0);
+    }
+
+    #[test]
+    fn tables_do_not_drift() {
+        let start = Temperature::celsius(0.0).unwrap();
+        let end = Temperature::celsius(1.0).unwrap();
+
+        let rows = table(start, end, 0.1).unwrap();
+        assert_eq!(rows.len(), 11);
+        assert_close(rows[10].value(), 1.0);
+    }
+
+    #[test]
+    fn tables_need_a_sensible_step_and_size() {
+        let start = Temperature::celsius(0.0).unwrap();
+        let end = Temperature::celsius(10.0).unwrap();
+        for step in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                table(start, end, step),
+                Err(TemperatureError::InvalidStep(_))
+            ));
+        }
+
+        let last = Temperature::celsius((MAX_TABLE_ROWS - 1) as f64).unwrap();
+        assert_eq!(table(start, last, 1.0).unwrap().len(), MAX_TABLE_ROWS);
+
+        let past = Temperature::celsius(MAX_TABLE_ROWS as f64).unwrap();
+        assert_eq!(
+            table(start, past, 1.0),
+            Err(TemperatureError::TooManyRows(MAX_TABLE_ROWS + 1))
+        );
+    }
+}
@@ -0,0 +1,179 @@
+use crate::song::Format;
+use crate::stats::{self, Loop};
+use std::path::PathBuf;
+
+const DEFAULT_BINS: usize = 10;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    /// Print the chapter's listing: every element of an array, in a `for`
+    /// loop. This is what running with no arguments does.
+    Listing,
+    /// Print the song described in `path`.
+    Song {
+        path: PathBuf,
+        format: Format,
+        /// Only the first this many verses.
+        verses: Option<usize>,
+    },
+    /// Summarize the integers in `path`, or on stdin.
+    Stats {
+        path: Option<PathBuf>,
+        percentiles: Vec<f64>,
+        bins: usize,
+        /// One kind of loop, or `None` to run both and check they agree.
+        loop_kind: Option<Loop>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    pub command: Command,
+}
+
+impl Config {
+    /// Parses the full argument list, including the program name.
+    pub fn build(mut args: impl Iterator<Item = String>) -> Result<Config, String> {
+        args.next();
+
+        let command = match args.next().as_deref() {
+            Some("song") => build_song(args)?,
+            Some("stats") => build_stats(args)?,
+            Some(other) => return Err(format!("unknown command: {other}")),
+            None => Command::Listing,
+        };
+
+        Ok(Config { command })
+    }
+}
+
+fn build_song(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut path = None;
+    let mut format = Format::Text;
+    let mut verses = None;
+
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("missing value for {arg}"))
+        };
+
+        match arg.as_str() {
+            "--format" => {
+                let name = value()?;
+                format = Format::parse(&name).ok_or(format!("unknown format: {name}"))?;
+            }
+            "--verses" => {
+                let count = value()?;
+                verses = match count.parse() {
+                    Ok(0) | Err(_) => {
+                        return Err(format!("--verses expects a positive number, got {count:?}"))
+                    }
+                    Ok(count) => Some(count),
+                };
+            }
+            _ if arg.starts_with("--") => return Err(format!("unknown argument: {arg}")),
+            _ if path.is_some() => return Err(format!("unexpected argument: {arg}")),
+            _ => path = Some(PathBuf::from(arg)),
+        }
+    }
+
+    Ok(Command::Song {
+        path: path.ok_or("no song file given")?,
+        format,
+        verses,
+    })
+}
+
+fn build_stats(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut path = None;
+    let mut percentiles = Vec::new();
+    let mut bins = DEFAULT_BINS;
+    let mut loop_kind = None;
+
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("missing value for {arg}"))
+        };
+
+        match arg.as_str() {
+            "--percentile" => {
+                percentiles.push(stats::parse_percentile(&value()?).map_err(|err| err.to_string())?)
+            }
+            "--bins" => {
+                let count = value()?;
+                bins = match count.parse() {
+                    Ok(0) | Err(_) => {
+                        return Err(format!("--bins expects a positive number, got {count:?}"))
+                    }
+                    Ok(count) => count,
+                };
+            }
+            "--loop" => {
+                let name = value()?;
+                loop_kind = match name.as_str() {
+                    "both" => None,
+                    _ => Some(Loop::parse(&name).ok_or(format!("unknown loop: {name}"))?),
+                };
+            }
+            // `-` is stdin, as if no file were given.
+            "-" if path.is_none() => {}
+            _ if arg.starts_with("--") => return Err(format!("unknown argument: {arg}")),
+            _ if path.is_some() => return Err(format!("unexpected argument: {arg}")),
+            _ => path = Some(PathBuf::from(arg)),
+        }
+    }
+
+    if percentiles.is_empty() {
+        percentiles = stats::DEFAULT_PERCENTILES.to_vec();
+    }
+
+    Ok(Command::Stats {
+        path,
+        percentiles,
+        bins,
+        loop_kind,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(args: &[&str]) -> Result<Command, String> {
+        let args = ["loops"].iter().chain(args).map(|arg| arg.to_string());
+        Config::build(args).map(|config| config.command)
+    }
+
+    #[test]
+    fn runs_the_listing_without_arguments() {
+        assert_eq!(build(&[]), Ok(Command::Listing));
+    }
+
+    #[test]
+    fn builds_each_command() {
+        assert_eq!(
+            build(&["song", "songs/twelve_days.txt", "--verses", "3"]),
+            Ok(Command::Song {
+                path: PathBuf::from("songs/twelve_days.txt"),
+                format: Format::Text,
+                verses: Some(3),
+            })
+        );
+        assert_eq!(
+            build(&["stats", "-", "--loop", "while"]),
+            Ok(Command::Stats {
+                path: None,
+                percentiles: vec![25.0, 50.0, 75.0, 90.0],
+                bins: DEFAULT_BINS,
+                loop_kind: Some(Loop::While),
+            })
+        );
+        assert_eq!(
+            build(&["dance"]),
+            Err(String::from("unknown command: dance"))
+        );
+        assert_eq!(build(&["song"]), Err(String::from("no song file given")));
+    }
+}
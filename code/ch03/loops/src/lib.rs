@@ -0,0 +1,7 @@
+pub mod config;
+pub mod song;
+pub mod stats;
+
+pub use crate::config::{Command, Config};
+pub use crate::song::{Format, Song, SongError};
+pub use crate::stats::{Loop, StatsError, Summary};
@@ -1,7 +1,136 @@
+use loops::stats::{self, for_loop, while_loop, Loop, StatsError, Summary};
+use loops::{Command, Config, Song};
+use std::fs::{self, File};
+use std::path::Path;
+use std::{env, io, process};
+
 fn main() {
+    let config = Config::build(env::args()).unwrap_or_else(|err| {
+        eprintln!("Problem parsing arguments: {err}");
+        eprintln!("Usage: loops");
+        eprintln!("       loops song FILE [--format text|markdown] [--verses N]");
+        eprintln!(
+            "       loops stats [FILE] [--percentile P]... [--bins N] [--loop for|while|both]"
+        );
+        eprintln!("Without a command, prints the chapter's listing.");
+        eprintln!("Song files live in songs/, such as songs/twelve_days.txt.");
+        eprintln!("Without a FILE, or with -, stats reads integers from stdin.");
+        process::exit(2);
+    });
+
+    match config.command {
+        Command::Listing => listing(),
+        Command::Song {
+            path,
+            format,
+            verses,
+        } => {
+            let song = fs::read_to_string(&path)
+                .map_err(|err| err.to_string())
+                .and_then(|source| source.parse::<Song>().map_err(|err| err.to_string()))
+                .unwrap_or_else(|err| {
+                    eprintln!("Problem reading {}: {err}", path.display());
+                    process::exit(1);
+                });
+
+            print!("{}", song.render(format, verses));
+        }
+        Command::Stats {
+            path,
+            percentiles,
+            bins,
+            loop_kind,
+        } => {
+            let values = read_values(path.as_deref()).unwrap_or_else(|err| {
+                let source = match &path {
+                    Some(path) => path.display().to_string(),
+                    None => String::from("stdin"),
+                };
+                eprintln!("Problem reading {source}: {err}");
+                process::exit(1);
+            });
+
+            let summary = summarize(&values, loop_kind).unwrap_or_else(|err| {
+                eprintln!("Problem summarizing the values: {err}");
+                process::exit(1);
+            });
+
+            print_stats(
+                &values,
+                &summary,
+                &percentiles,
+                bins,
+                loop_kind.unwrap_or(Loop::For),
+            );
+        }
+    }
+}
+
+fn listing() {
     let a = [10, 20, 30, 40, 50];
 
     for element in a {
         println!("the value is: {element}");
     }
-}
\ No newline at end of file
+}
+
+fn read_values(path: Option<&Path>) -> Result<Vec<i64>, String> {
+    let values = match path {
+        Some(path) => File::open(path).and_then(stats::read_values),
+        None => stats::read_values(io::stdin()),
+    };
+
+    values
+        .map_err(|err| err.to_string())?
+        .map_err(|err| err.to_string())
+}
+
+/// With no loop chosen, runs both and refuses to go on if they disagree.
+fn summarize(values: &[i64], loop_kind: Option<Loop>) -> Result<Summary, String> {
+    if let Some(kind) = loop_kind {
+        return Summary::of(values, kind).map_err(|err| err.to_string());
+    }
+
+    match Summary::compare(values).map_err(|err| err.to_string())? {
+        Ok(summary) => Ok(summary),
+        Err((expected, actual)) => Err(format!(
+            "the {} and {} loops disagree:\n{expected:?}\n{actual:?}",
+            Loop::For.name(),
+            Loop::While.name()
+        )),
+    }
+}
+
+/// The percentiles and histogram use `kind` too.
+fn print_stats(values: &[i64], summary: &Summary, percentiles: &[f64], bins: usize, kind: Loop) {
+    println!("count     {}", summary.count);
+    println!("min       {}", summary.min);
+    println!("max       {}", summary.max);
+    match &summary.sum {
+        Ok(sum) => println!("sum       {sum}"),
+        Err(StatsError::Overflow) => println!("sum       overflows i64"),
+        Err(err) => println!("sum       {err}"),
+    }
+    println!("mean      {:.2}", summary.mean);
+    println!("variance  {:.2}", summary.variance);
+    println!("std dev   {:.2}", summary.standard_deviation());
+
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable();
+    let (percentiles, histogram) = match kind {
+        Loop::For => (
+            for_loop::percentiles(&sorted, percentiles),
+            for_loop::histogram(values, bins),
+        ),
+        Loop::While => (
+            while_loop::percentiles(&sorted, percentiles),
+            while_loop::histogram(values, bins),
+        ),
+    };
+    for (p, value) in percentiles {
+        println!("{:<9} {value:.2}", format!("p{p}"));
+    }
+
+    println!();
+    print!("{}", stats::render_histogram(&histogram));
+}
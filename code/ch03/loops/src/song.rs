@@ -0,0 +1,473 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+const ORDINALS: [&str; 20] = [
+    "first",
+    "second",
+    "third",
+    "fourth",
+    "fifth",
+    "sixth",
+    "seventh",
+    "eighth",
+    "ninth",
+    "tenth",
+    "eleventh",
+    "twelfth",
+    "thirteenth",
+    "fourteenth",
+    "fifteenth",
+    "sixteenth",
+    "seventeenth",
+    "eighteenth",
+    "nineteenth",
+    "twentieth",
+];
+
+const CARDINALS: [&str; 20] = [
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+    "twenty",
+];
+
+/// Every verse needs an ordinal and a cardinal, so a song can only have as
+/// many items as there are words for them.
+pub const MAX_ITEMS: usize = ORDINALS.len();
+
+/// What `{name}` placeholders in `verse:` and `end:` lines can say.
+const PLACEHOLDERS: [&str; 5] = ["n", "ordinal", "Ordinal", "cardinal", "Cardinal"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Markdown,
+}
+
+impl Format {
+    pub fn parse(name: &str) -> Option<Format> {
+        match name {
+            "text" | "txt" => Some(Format::Text),
+            "markdown" | "md" => Some(Format::Markdown),
+            _ => None,
+        }
+    }
+}
+
+/// Why a song file could not be read. Line numbers start at 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SongError {
+    MissingTitle,
+    MissingVerse,
+    NoItems,
+    TooManyItems(usize),
+    /// A header line that is not `key: value`.
+    Malformed(usize),
+    UnknownKey {
+        line: usize,
+        key: String,
+    },
+    DuplicateKey {
+        line: usize,
+        key: String,
+    },
+    UnknownPlaceholder {
+        line: usize,
+        name: String,
+    },
+}
+
+impl fmt::Display for SongError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SongError::MissingTitle => write!(f, "the song has no title"),
+            SongError::MissingVerse => write!(f, "the song has no verse lines"),
+            SongError::NoItems => write!(f, "the song has no items after ---"),
+            SongError::TooManyItems(count) => {
+                write!(
+                    f,
+                    "the song has {count} items; at most {MAX_ITEMS} are supported"
+                )
+            }
+            SongError::Malformed(line) => write!(f, "line {line}: expected key: value"),
+            SongError::UnknownKey { line, key } => write!(f, "line {line}: unknown key {key:?}"),
+            SongError::DuplicateKey { line, key } => {
+                write!(f, "line {line}: {key} is already set")
+            }
+            SongError::UnknownPlaceholder { line, name } => {
+                write!(f, "line {line}: unknown placeholder {{{name}}}")
+            }
+        }
+    }
+}
+
+impl Error for SongError {}
+
+/// A cumulative song: verse `n` introduces item `n`, then repeats every
+/// earlier item, newest first.
+///
+/// Song files start with `key: value` lines, then `---`, then one item per
+/// line. Blank lines and lines starting with `#` are skipped.
+///
+/// - `title:` names the song.
+/// - `verse:` opens every verse; repeat it for several lines.
+/// - `closing:` replaces the first item in every verse but the first, as
+///   in "And a partridge in a pear tree".
+/// - `end:` closes every verse; it may also repeat.
+///
+/// `verse:` and `end:` lines may use `{n}`, `{ordinal}` and `{cardinal}`;
+/// capitalize the placeholder to capitalize the word.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Song {
+    pub title: String,
+    pub verse: Vec<String>,
+    pub closing: Option<String>,
+    pub end: Vec<String>,
+    pub items: Vec<String>,
+}
+
+impl FromStr for Song {
+    type Err = SongError;
+
+    fn from_str(source: &str) -> Result<Song, SongError> {
+        let mut title = None;
+        let mut verse = Vec::new();
+        let mut closing = None;
+        let mut end = Vec::new();
+        let mut items = Vec::new();
+        let mut in_items = false;
+
+        for (index, line) in source.lines().enumerate() {
+            let number = index + 1;
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if in_items {
+                items.push(line.to_string());
+                continue;
+            }
+            if line == "---" {
+                in_items = true;
+                continue;
+            }
+
+            let (key, value) = line.split_once(':').ok_or(SongError::Malformed(number))?;
+            let (key, value) = (key.trim(), value.trim().to_string());
+
+            match key {
+                "title" | "closing" => {
+                    let slot = if key == "title" {
+                        &mut title
+                    } else {
+                        &mut closing
+                    };
+                    if slot.is_some() {
+                        return Err(SongError::DuplicateKey {
+                            line: number,
+                            key: key.to_string(),
+                        });
+                    }
+                    *slot = Some(value);
+                }
+                "verse" | "end" => {
+                    check_placeholders(&value, number)?;
+                    if key == "verse" {
+                        verse.push(value);
+                    } else {
+                        end.push(value);
+                    }
+                }
+                _ => {
+                    return Err(SongError::UnknownKey {
+                        line: number,
+                        key: key.to_string(),
+                    })
+                }
+            }
+        }
+
+        let title = title.ok_or(SongError::MissingTitle)?;
+        if verse.is_empty() {
+            return Err(SongError::MissingVerse);
+        }
+        if items.is_empty() {
+            return Err(SongError::NoItems);
+        }
+        if items.len() > MAX_ITEMS {
+            return Err(SongError::TooManyItems(items.len()));
+        }
+
+        Ok(Song {
+            title,
+            verse,
+            closing,
+            end,
+            items,
+        })
+    }
+}
+
+fn check_placeholders(template: &str, line: usize) -> Result<(), SongError> {
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        let after = &rest[start + 1..];
+        let Some(close) = after.find('}') else {
+            break;
+        };
+
+        let name = &after[..close];
+        if !PLACEHOLDERS.contains(&name) {
+            return Err(SongError::UnknownPlaceholder {
+                line,
+                name: name.to_string(),
+            });
+        }
+        rest = &after[close + 1..];
+    }
+
+    Ok(())
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Fills in the placeholders for verse `n`, counting from 1.
+fn fill(template: &str, n: usize) -> String {
+    let ordinal = ORDINALS[n - 1];
+    let cardinal = CARDINALS[n - 1];
+
+    template
+        .replace("{n}", &n.to_string())
+        .replace("{ordinal}", ordinal)
+        .replace("{Ordinal}", &capitalize(ordinal))
+        .replace("{cardinal}", cardinal)
+        .replace("{Cardinal}", &capitalize(cardinal))
+}
+
+impl Song {
+    /// Verse `n`, counting from 1, one string per line.
+    ///
+    /// # Panics
+    ///
+    /// If `n` is zero or more than the number of items.
+    pub fn verse(&self, n: usize) -> Vec<String> {
+        assert!(
+            (1..=self.items.len()).contains(&n),
+            "verse {n} of a {}-verse song",
+            self.items.len()
+        );
+
+        let mut lines = Vec::new();
+
+        for template in &self.verse {
+            lines.push(fill(template, n));
+        }
+
+        for index in (0..n).rev() {
+            let item = match &self.closing {
+                Some(closing) if index == 0 && n > 1 => closing,
+                _ => &self.items[index],
+            };
+            lines.push(item.clone());
+        }
+
+        for template in &self.end {
+            lines.push(fill(template, n));
+        }
+
+        lines
+    }
+
+    /// The first `verses` verses, or all of them, ready to print.
+    pub fn render(&self, format: Format, verses: Option<usize>) -> String {
+        let count = verses.unwrap_or(self.items.len()).min(self.items.len());
+        let mut output = String::new();
+
+        match format {
+            Format::Text => output += &format!("{}\n", self.title),
+            Format::Markdown => output += &format!("# {}\n", self.title),
+        }
+
+        for n in 1..=count {
+            output.push('\n');
+            if format == Format::Markdown {
+                output += &format!("## Verse {n}\n\n");
+            }
+
+            let lines = self.verse(n);
+            for (index, line) in lines.iter().enumerate() {
+                output += line;
+                // A trailing backslash is a hard line break in Markdown;
+                // without it the verse would run together as one line.
+                if format == Format::Markdown && index + 1 < lines.len() {
+                    output.push('\\');
+                }
+                output.push('\n');
+            }
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TWELVE_DAYS: &str = include_str!("../songs/twelve_days.txt");
+    const RUSHES: &str = include_str!("../songs/green_grow_the_rushes.txt");
+
+    fn song(source: &str) -> Song {
+        source.parse().unwrap()
+    }
+
+    #[test]
+    fn renders_the_twelve_days_as_checked_in() {
+        let song = song(TWELVE_DAYS);
+
+        assert_eq!(
+            song.render(Format::Text, None),
+            include_str!("../songs/expected/twelve_days.txt")
+        );
+        assert_eq!(
+            song.render(Format::Markdown, None),
+            include_str!("../songs/expected/twelve_days.md")
+        );
+    }
+
+    #[test]
+    fn renders_green_grow_the_rushes_as_checked_in() {
+        let song = song(RUSHES);
+
+        assert_eq!(
+            song.render(Format::Text, None),
+            include_str!("../songs/expected/green_grow_the_rushes.txt")
+        );
+        assert_eq!(
+            song.render(Format::Markdown, None),
+            include_str!("../songs/expected/green_grow_the_rushes.md")
+        );
+    }
+
+    #[test]
+    fn renders_only_the_verses_asked_for() {
+        let song = song(TWELVE_DAYS);
+
+        assert_eq!(
+            song.render(Format::Text, Some(2)),
+            "The Twelve Days of Christmas\n\n\
+             On the first day of Christmas my true love sent to me:\n\
+             A partridge in a pear tree.\n\n\
+             On the second day of Christmas my true love sent to me:\n\
+             Two turtle doves,\n\
+             And a partridge in a pear tree.\n"
+        );
+        assert_eq!(
+            song.render(Format::Text, Some(100)),
+            song.render(Format::Text, None)
+        );
+    }
+
+    #[test]
+    fn rejects_keys_set_twice() {
+        let source = "title: One\ntitle: Two\nverse: x\n---\nitem\n";
+
+        assert_eq!(
+            source.parse::<Song>(),
+            Err(SongError::DuplicateKey {
+                line: 2,
+                key: String::from("title"),
+            })
+        );
+
+        let source = "title: One\nclosing: a\nverse: x\n\nclosing: b\n---\nitem\n";
+        assert_eq!(
+            source.parse::<Song>(),
+            Err(SongError::DuplicateKey {
+                line: 5,
+                key: String::from("closing"),
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_placeholders() {
+        let source = "title: T\nverse: On day {n} of {month}\n---\nitem\n";
+
+        let err = source.parse::<Song>().unwrap_err();
+        assert_eq!(
+            err,
+            SongError::UnknownPlaceholder {
+                line: 2,
+                name: String::from("month"),
+            }
+        );
+        assert_eq!(err.to_string(), "line 2: unknown placeholder {month}");
+
+        let source = "title: T\nverse: x\nend: {ORDINAL}\n---\nitem\n";
+        assert!(matches!(
+            source.parse::<Song>(),
+            Err(SongError::UnknownPlaceholder { line: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_more_items_than_there_are_ordinals() {
+        let mut source = String::from("title: T\nverse: x\n---\n");
+        for item in 1..=MAX_ITEMS {
+            source += &format!("item {item}\n");
+        }
+        assert!(source.parse::<Song>().is_ok());
+
+        source += "one too many\n";
+        assert_eq!(
+            source.parse::<Song>(),
+            Err(SongError::TooManyItems(MAX_ITEMS + 1))
+        );
+    }
+
+    #[test]
+    fn rejects_incomplete_songs() {
+        assert_eq!(
+            "verse: x\n---\nitem".parse::<Song>(),
+            Err(SongError::MissingTitle)
+        );
+        assert_eq!(
+            "title: T\n---\nitem".parse::<Song>(),
+            Err(SongError::MissingVerse)
+        );
+        assert_eq!(
+            "title: T\nverse: x\n---\n".parse::<Song>(),
+            Err(SongError::NoItems)
+        );
+        assert_eq!(
+            "title: T\nchorus\n".parse::<Song>(),
+            Err(SongError::Malformed(2))
+        );
+    }
+}
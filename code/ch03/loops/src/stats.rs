@@ -0,0 +1,581 @@
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Read};
+
+/// Percentiles shown when none are asked for.
+pub const DEFAULT_PERCENTILES: [f64; 4] = [25.0, 50.0, 75.0, 90.0];
+
+/// Width of the longest histogram bar, in characters.
+const BAR_WIDTH: usize = 40;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StatsError {
+    Empty,
+    /// The values add up to more than an `i64` can hold.
+    Overflow,
+    /// Input that is not an integer, with its line number.
+    NotAnInteger {
+        line: usize,
+        text: String,
+    },
+    /// A percentile outside 0 to 100.
+    BadPercentile(String),
+}
+
+impl fmt::Display for StatsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StatsError::Empty => write!(f, "there are no values"),
+            StatsError::Overflow => write!(f, "the sum does not fit in an i64"),
+            StatsError::NotAnInteger { line, text } => {
+                write!(f, "line {line}: {text:?} is not an integer")
+            }
+            StatsError::BadPercentile(text) => {
+                write!(f, "{text:?} is not a percentile from 0 to 100")
+            }
+        }
+    }
+}
+
+impl Error for StatsError {}
+
+/// Which kind of loop computes the summary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Loop {
+    /// `for value in values`, as in the chapter's listing.
+    For,
+    /// `while index < values.len()`, the version the chapter warns about.
+    While,
+}
+
+impl Loop {
+    pub const ALL: [Loop; 2] = [Loop::For, Loop::While];
+
+    pub fn parse(name: &str) -> Option<Loop> {
+        match name {
+            "for" => Some(Loop::For),
+            "while" => Some(Loop::While),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Loop::For => "for",
+            Loop::While => "while",
+        }
+    }
+}
+
+/// Each function walks the slice with a `for` loop.
+pub mod for_loop {
+    use super::{percentile, Bin, StatsError};
+
+    pub fn min(values: &[i64]) -> Option<i64> {
+        let mut min = *values.first()?;
+        for &value in values {
+            if value < min {
+                min = value;
+            }
+        }
+        Some(min)
+    }
+
+    pub fn max(values: &[i64]) -> Option<i64> {
+        let mut max = *values.first()?;
+        for &value in values {
+            if value > max {
+                max = value;
+            }
+        }
+        Some(max)
+    }
+
+    pub fn sum(values: &[i64]) -> Result<i64, StatsError> {
+        let mut sum: i64 = 0;
+        for &value in values {
+            sum = sum.checked_add(value).ok_or(StatsError::Overflow)?;
+        }
+        Ok(sum)
+    }
+
+    /// Adds up in `i128`, so it works even when `sum` overflows.
+    pub fn mean(values: &[i64]) -> Option<f64> {
+        if values.is_empty() {
+            return None;
+        }
+
+        let mut sum: i128 = 0;
+        for &value in values {
+            sum += i128::from(value);
+        }
+        Some(sum as f64 / values.len() as f64)
+    }
+
+    /// The population variance: the mean squared distance from the mean.
+    pub fn variance(values: &[i64]) -> Option<f64> {
+        let mean = mean(values)?;
+
+        let mut total = 0.0;
+        for &value in values {
+            let difference = value as f64 - mean;
+            total += difference * difference;
+        }
+        Some(total / values.len() as f64)
+    }
+
+    /// Each of `wanted` paired with its value in `sorted`; nothing when
+    /// `sorted` is empty.
+    pub fn percentiles(sorted: &[i64], wanted: &[f64]) -> Vec<(f64, f64)> {
+        let mut found = Vec::new();
+        for &p in wanted {
+            if let Some(value) = percentile(sorted, p) {
+                found.push((p, value));
+            }
+        }
+        found
+    }
+
+    /// Splits the range of `values` into at most `bins` bins of equal width.
+    pub fn histogram(values: &[i64], bins: usize) -> Vec<Bin> {
+        let (Some(min), Some(max)) = (self::min(values), self::max(values)) else {
+            return Vec::new();
+        };
+
+        // Work in i128 so `max - min` cannot overflow for extreme values.
+        let (min, max) = (i128::from(min), i128::from(max));
+        let span = max - min + 1;
+        let bins = bins.max(1) as i128;
+        let width = (span + bins - 1) / bins;
+
+        let mut histogram = Vec::new();
+        for index in 0..(span + width - 1) / width {
+            let start = min + index * width;
+            histogram.push(Bin {
+                start: start as i64,
+                end: (start + width - 1).min(max) as i64,
+                count: 0,
+            });
+        }
+
+        for &value in values {
+            let index = (i128::from(value) - min) / width;
+            histogram[index as usize].count += 1;
+        }
+
+        histogram
+    }
+}
+
+/// The same functions as `for_loop`, walking the slice by index. The
+/// answers must match exactly; `Summary::compare` checks that they do.
+pub mod while_loop {
+    use super::{percentile, Bin, StatsError};
+
+    pub fn min(values: &[i64]) -> Option<i64> {
+        let mut min = *values.first()?;
+        let mut index = 1;
+        while index < values.len() {
+            if values[index] < min {
+                min = values[index];
+            }
+            index += 1;
+        }
+        Some(min)
+    }
+
+    pub fn max(values: &[i64]) -> Option<i64> {
+        let mut max = *values.first()?;
+        let mut index = 1;
+        while index < values.len() {
+            if values[index] > max {
+                max = values[index];
+            }
+            index += 1;
+        }
+        Some(max)
+    }
+
+    pub fn sum(values: &[i64]) -> Result<i64, StatsError> {
+        let mut sum: i64 = 0;
+        let mut index = 0;
+        while index < values.len() {
+            sum = sum.checked_add(values[index]).ok_or(StatsError::Overflow)?;
+            index += 1;
+        }
+        Ok(sum)
+    }
+
+    pub fn mean(values: &[i64]) -> Option<f64> {
+        if values.is_empty() {
+            return None;
+        }
+
+        let mut sum: i128 = 0;
+        let mut index = 0;
+        while index < values.len() {
+            sum += i128::from(values[index]);
+            index += 1;
+        }
+        Some(sum as f64 / values.len() as f64)
+    }
+
+    pub fn variance(values: &[i64]) -> Option<f64> {
+        let mean = mean(values)?;
+
+        let mut total = 0.0;
+        let mut index = 0;
+        while index < values.len() {
+            let difference = values[index] as f64 - mean;
+            total += difference * difference;
+            index += 1;
+        }
+        Some(total / values.len() as f64)
+    }
+
+    pub fn percentiles(sorted: &[i64], wanted: &[f64]) -> Vec<(f64, f64)> {
+        let mut found = Vec::new();
+        let mut index = 0;
+        while index < wanted.len() {
+            if let Some(value) = percentile(sorted, wanted[index]) {
+                found.push((wanted[index], value));
+            }
+            index += 1;
+        }
+        found
+    }
+
+    pub fn histogram(values: &[i64], bins: usize) -> Vec<Bin> {
+        let (Some(min), Some(max)) = (self::min(values), self::max(values)) else {
+            return Vec::new();
+        };
+
+        let (min, max) = (i128::from(min), i128::from(max));
+        let span = max - min + 1;
+        let bins = bins.max(1) as i128;
+        let width = (span + bins - 1) / bins;
+
+        let mut histogram = Vec::new();
+        let mut start = min;
+        while start <= max {
+            histogram.push(Bin {
+                start: start as i64,
+                end: (start + width - 1).min(max) as i64,
+                count: 0,
+            });
+            start += width;
+        }
+
+        let mut index = 0;
+        while index < values.len() {
+            let bin = (i128::from(values[index]) - min) / width;
+            histogram[bin as usize].count += 1;
+            index += 1;
+        }
+
+        histogram
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    pub count: usize,
+    pub min: i64,
+    pub max: i64,
+    /// `Err(StatsError::Overflow)` when the values do not add up in an `i64`.
+    pub sum: Result<i64, StatsError>,
+    pub mean: f64,
+    pub variance: f64,
+}
+
+impl Summary {
+    pub fn of(values: &[i64], kind: Loop) -> Result<Summary, StatsError> {
+        let summary = match kind {
+            Loop::For => Summary {
+                count: values.len(),
+                min: for_loop::min(values).ok_or(StatsError::Empty)?,
+                max: for_loop::max(values).ok_or(StatsError::Empty)?,
+                sum: for_loop::sum(values),
+                mean: for_loop::mean(values).ok_or(StatsError::Empty)?,
+                variance: for_loop::variance(values).ok_or(StatsError::Empty)?,
+            },
+            Loop::While => Summary {
+                count: values.len(),
+                min: while_loop::min(values).ok_or(StatsError::Empty)?,
+                max: while_loop::max(values).ok_or(StatsError::Empty)?,
+                sum: while_loop::sum(values),
+                mean: while_loop::mean(values).ok_or(StatsError::Empty)?,
+                variance: while_loop::variance(values).ok_or(StatsError::Empty)?,
+            },
+        };
+
+        Ok(summary)
+    }
+
+    /// Summarizes `values` with every kind of loop and returns the first
+    /// summary, or both summaries if any two disagree.
+    pub fn compare(values: &[i64]) -> Result<Result<Summary, (Summary, Summary)>, StatsError> {
+        let expected = Summary::of(values, Loop::For)?;
+        let actual = Summary::of(values, Loop::While)?;
+
+        if expected == actual {
+            Ok(Ok(expected))
+        } else {
+            Ok(Err((expected, actual)))
+        }
+    }
+
+    pub fn standard_deviation(&self) -> f64 {
+        self.variance.sqrt()
+    }
+}
+
+/// Parses `25`, `50.5` or `p90` as a percentile from 0 to 100.
+pub fn parse_percentile(text: &str) -> Result<f64, StatsError> {
+    let bad = || StatsError::BadPercentile(text.to_string());
+
+    let percentile: f64 = text
+        .trim()
+        .trim_start_matches(['p', 'P'])
+        .parse()
+        .map_err(|_| bad())?;
+    if !(0.0..=100.0).contains(&percentile) {
+        return Err(bad());
+    }
+
+    Ok(percentile)
+}
+
+/// Interpolates between the two nearest values, so the 50th percentile of
+/// `[1, 2, 3, 4]` is 2.5.
+///
+/// `sorted` must be sorted and `percentile` between 0 and 100.
+pub fn percentile(sorted: &[i64], percentile: f64) -> Option<f64> {
+    let last = sorted.len().checked_sub(1)?;
+
+    let rank = percentile / 100.0 * last as f64;
+    let below = rank.floor() as usize;
+    let above = rank.ceil() as usize;
+    let fraction = rank - below as f64;
+
+    let low = sorted[below] as f64;
+    let high = sorted[above] as f64;
+    Some(low + (high - low) * fraction)
+}
+
+/// One histogram row: the values from `start` up to and including `end`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bin {
+    pub start: i64,
+    pub end: i64,
+    pub count: usize,
+}
+
+/// Draws each bin as a bar of `#`, scaled so the fullest is `BAR_WIDTH`.
+pub fn render_histogram(histogram: &[Bin]) -> String {
+    let fullest = histogram.iter().map(|bin| bin.count).max().unwrap_or(0);
+    let label_width = histogram
+        .iter()
+        .map(|bin| bin.start.to_string().len().max(bin.end.to_string().len()))
+        .max()
+        .unwrap_or(0);
+
+    let mut output = String::new();
+    for bin in histogram {
+        let bar = if fullest == 0 {
+            0
+        } else {
+            // Round up so a bin with anything in it is never blank.
+            (bin.count * BAR_WIDTH).div_ceil(fullest)
+        };
+
+        output += &format!(
+            "{:>label_width$} ..= {:>label_width$} | {:<BAR_WIDTH$} {}\n",
+            bin.start,
+            bin.end,
+            "#".repeat(bar),
+            bin.count
+        );
+    }
+
+    output
+}
+
+/// Reads whitespace- or comma-separated integers. `#` starts a comment.
+pub fn parse_values(source: &str) -> Result<Vec<i64>, StatsError> {
+    let mut values = Vec::new();
+
+    for (index, line) in source.lines().enumerate() {
+        let line = match line.split_once('#') {
+            Some((before, _)) => before,
+            None => line,
+        };
+
+        for text in line.split(|c: char| c.is_whitespace() || c == ',') {
+            if text.is_empty() {
+                continue;
+            }
+
+            let value = text.parse().map_err(|_| StatsError::NotAnInteger {
+                line: index + 1,
+                text: text.to_string(),
+            })?;
+            values.push(value);
+        }
+    }
+
+    Ok(values)
+}
+
+/// Reads all of `reader`, usually a file or stdin.
+pub fn read_values(mut reader: impl Read) -> io::Result<Result<Vec<i64>, StatsError>> {
+    let mut source = String::new();
+    reader.read_to_string(&mut source)?;
+
+    Ok(parse_values(&source))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A small xorshift generator, so the slices are the same every run.
+    fn generate(seed: u64, len: usize, limit: i64) -> Vec<i64> {
+        let mut state = seed.max(1);
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                (state % (2 * limit as u64 + 1)) as i64 - limit
+            })
+            .collect()
+    }
+
+    fn assert_loops_agree(values: &[i64]) {
+        assert_eq!(for_loop::min(values), while_loop::min(values));
+        assert_eq!(for_loop::max(values), while_loop::max(values));
+        assert_eq!(for_loop::sum(values), while_loop::sum(values));
+        assert_eq!(for_loop::mean(values), while_loop::mean(values));
+        assert_eq!(for_loop::variance(values), while_loop::variance(values));
+        assert_eq!(
+            for_loop::histogram(values, 7),
+            while_loop::histogram(values, 7)
+        );
+
+        let mut sorted = values.to_vec();
+        sorted.sort_unstable();
+        let wanted = [0.0, 10.0, 25.0, 50.0, 90.0, 100.0];
+        assert_eq!(
+            for_loop::percentiles(&sorted, &wanted),
+            while_loop::percentiles(&sorted, &wanted)
+        );
+    }
+
+    #[test]
+    fn loops_agree_on_generated_slices() {
+        for seed in 1..50 {
+            let values = generate(seed, seed as usize * 7, 1_000);
+            assert_loops_agree(&values);
+            assert!(matches!(Summary::compare(&values), Ok(Ok(_))));
+        }
+    }
+
+    #[test]
+    fn empty_input_has_no_summary() {
+        assert_loops_agree(&[]);
+        assert_eq!(for_loop::min(&[]), None);
+        assert_eq!(for_loop::sum(&[]), Ok(0));
+        assert_eq!(for_loop::histogram(&[], 3), []);
+        assert_eq!(Summary::of(&[], Loop::For), Err(StatsError::Empty));
+        assert_eq!(Summary::of(&[], Loop::While), Err(StatsError::Empty));
+        assert_eq!(percentile(&[], 50.0), None);
+    }
+
+    #[test]
+    fn single_values_summarize_to_themselves() {
+        for value in [0, -7, i64::MIN, i64::MAX] {
+            assert_loops_agree(&[value]);
+            for kind in Loop::ALL {
+                let summary = Summary::of(&[value], kind).unwrap();
+                assert_eq!((summary.min, summary.max), (value, value));
+                assert_eq!(summary.sum, Ok(value));
+                assert_eq!(summary.mean, value as f64);
+                assert_eq!(summary.variance, 0.0);
+            }
+            assert_eq!(
+                for_loop::histogram(&[value], 4),
+                [Bin {
+                    start: value,
+                    end: value,
+                    count: 1
+                }]
+            );
+        }
+    }
+
+    #[test]
+    fn sums_past_the_ends_of_i64_overflow() {
+        let high = [i64::MAX, 1];
+        let low = [i64::MIN, -1];
+        let both = [i64::MAX, i64::MIN, i64::MAX, i64::MIN];
+
+        for values in [&high[..], &low[..]] {
+            assert_loops_agree(values);
+            assert_eq!(for_loop::sum(values), Err(StatsError::Overflow));
+            assert_eq!(
+                Summary::of(values, Loop::While).unwrap().sum,
+                Err(StatsError::Overflow)
+            );
+        }
+
+        // The running sum never leaves the range, so this one is fine.
+        assert_loops_agree(&both);
+        assert_eq!(for_loop::sum(&both), Ok(-2));
+        assert_eq!(for_loop::sum(&[i64::MAX, -1, 1]), Ok(i64::MAX));
+
+        // The mean adds up in i128, so it survives the overflow.
+        assert_eq!(for_loop::mean(&[i64::MAX, i64::MAX]), Some(i64::MAX as f64));
+        assert_eq!(
+            while_loop::mean(&[i64::MIN, i64::MIN]),
+            Some(i64::MIN as f64)
+        );
+    }
+
+    #[test]
+    fn histograms_cover_the_whole_i64_range() {
+        let values = [i64::MIN, -1, 0, i64::MAX];
+        assert_loops_agree(&values);
+
+        let histogram = for_loop::histogram(&values, 2);
+        assert_eq!(
+            histogram,
+            [
+                Bin {
+                    start: i64::MIN,
+                    end: -1,
+                    count: 2
+                },
+                Bin {
+                    start: 0,
+                    end: i64::MAX,
+                    count: 2
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn percentiles_interpolate() {
+        let sorted = [1, 2, 3, 4];
+        assert_eq!(percentile(&sorted, 50.0), Some(2.5));
+        assert_eq!(percentile(&sorted, 0.0), Some(1.0));
+        assert_eq!(percentile(&sorted, 100.0), Some(4.0));
+        assert_eq!(
+            while_loop::percentiles(&sorted, &[25.0, 75.0]),
+            [(25.0, 1.75), (75.0, 3.25)]
+        );
+        assert_eq!(parse_percentile("p90"), Ok(90.0));
+        assert!(parse_percentile("101").is_err());
+    }
+}
@@ -0,0 +1,7 @@
+//! `"42".parse()` only works once the compiler knows which number type
+//! to make. `parse` works the same way, with richer errors and the
+//! notations people write in config files.
+
+pub mod number;
+
+pub use crate::number::{parse, ErrorKind, Number, ParseError};
@@ -1,3 +1,58 @@
-// fn main() {
-//     let guess: u32 = "42".parse().expect("Not a number!");
-// }
\ No newline at end of file
+use no_type_annotations::{parse, Number};
+use std::{env, process};
+
+/// Parses each value as `T`, printing the result or where it went wrong.
+fn show<T: Number>(values: &[String]) -> bool {
+    let mut all_parsed = true;
+
+    for input in values {
+        match parse::<T>(input) {
+            Ok(value) => println!("{input} = {value}"),
+            Err(err) => {
+                all_parsed = false;
+                eprintln!("{err}");
+                eprintln!("    {input}");
+                eprintln!("    {}^", " ".repeat(err.column() - 1));
+            }
+        }
+    }
+
+    all_parsed
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let Some((type_name, values)) = args.split_first().filter(|(_, values)| !values.is_empty())
+    else {
+        eprintln!("Usage: no_type_annotations TYPE VALUE...");
+        eprintln!("TYPE is any integer or float type, such as u8, i64 or f32.");
+        eprintln!("Values may use 0x, 0o and 0b prefixes and _ separators: 0xFF, 1_000.");
+        process::exit(2);
+    };
+
+    let all_parsed = match type_name.as_str() {
+        "u8" => show::<u8>(values),
+        "u16" => show::<u16>(values),
+        "u32" => show::<u32>(values),
+        "u64" => show::<u64>(values),
+        "u128" => show::<u128>(values),
+        "usize" => show::<usize>(values),
+        "i8" => show::<i8>(values),
+        "i16" => show::<i16>(values),
+        "i32" => show::<i32>(values),
+        "i64" => show::<i64>(values),
+        "i128" => show::<i128>(values),
+        "isize" => show::<isize>(values),
+        "f32" => show::<f32>(values),
+        "f64" => show::<f64>(values),
+        _ => {
+            eprintln!("Problem parsing arguments: unknown type: {type_name}");
+            process::exit(2);
+        }
+    };
+
+    if !all_parsed {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,519 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// What went wrong, without where; `ParseError` adds the position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// No digits at all, as in `""`, `"-"` or `"0x"`.
+    Empty,
+    InvalidDigit {
+        found: char,
+        radix: u32,
+    },
+    /// A `_` that is not between two digits, as in `_1`, `1__0` or `1_`.
+    MisplacedSeparator,
+    /// Larger than the type's maximum, which is kept as text.
+    Overflow {
+        max: String,
+    },
+    /// Smaller than the type's minimum, which is kept as text.
+    Underflow {
+        min: String,
+    },
+}
+
+/// Why `input` is not a number of type `type_name`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub kind: ErrorKind,
+    pub input: String,
+    /// The byte offset of the offending character in `input`.
+    pub position: usize,
+    pub type_name: &'static str,
+}
+
+impl ParseError {
+    fn new<T: Number>(kind: ErrorKind, input: &str, position: usize) -> ParseError {
+        ParseError {
+            kind,
+            input: input.to_string(),
+            position,
+            type_name: T::NAME,
+        }
+    }
+
+    /// The position counted in characters from 1, for messages.
+    pub fn column(&self) -> usize {
+        self.input[..self.position].chars().count() + 1
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (input, column, name) = (&self.input, self.column(), self.type_name);
+
+        match &self.kind {
+            ErrorKind::Empty if input.is_empty() => {
+                write!(f, "cannot parse an empty string as {name}")
+            }
+            ErrorKind::Empty => write!(f, "{input:?} has no digits at column {column}"),
+            ErrorKind::InvalidDigit { found, radix } => write!(
+                f,
+                "{input:?} has {found:?} at column {column}, which is not a base {radix} digit"
+            ),
+            ErrorKind::MisplacedSeparator => write!(
+                f,
+                "{input:?} has a `_` at column {column} that is not between two digits"
+            ),
+            ErrorKind::Overflow { max } => write!(
+                f,
+                "{input:?} is too large for {name}, which goes up to {max}; \
+                 it overflows at column {column}"
+            ),
+            ErrorKind::Underflow { min } => write!(
+                f,
+                "{input:?} is too small for {name}, which goes down to {min}; \
+                 it underflows at column {column}"
+            ),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// A type `parse` can produce. Every integer and float type implements it.
+pub trait Number: Copy + fmt::Display {
+    /// The type's name as written in Rust, such as `u16`.
+    const NAME: &'static str;
+    const MIN: Self;
+    const MAX: Self;
+
+    fn parse_number(input: &str) -> Result<Self, ParseError>;
+}
+
+/// Parses `input` as a `T`, which must be named somewhere, just as with
+/// `str::parse`:
+///
+/// ```
+/// use no_type_annotations::parse;
+///
+/// let port: u16 = parse("8_080").unwrap();
+/// let mask = parse::<u8>("0b1111_0000").unwrap();
+/// assert_eq!((port, mask), (8080, 240));
+/// ```
+///
+/// Integers take an optional sign and a `0x`, `0o` or `0b` prefix. Any
+/// number may have `_` between digits. Nothing else is allowed, not even
+/// surrounding whitespace.
+pub fn parse<T: Number>(input: &str) -> Result<T, ParseError> {
+    T::parse_number(input)
+}
+
+/// A run of digits in `radix`, each with its byte offset, possibly
+/// separated by single `_`s.
+struct Digits {
+    digits: Vec<(usize, u32)>,
+    /// Where the run stopped: the end of the input or the first character
+    /// that is neither a digit nor a `_`.
+    end: usize,
+}
+
+/// Reads digits from `start` until anything else, checking that every
+/// `_` sits between two digits.
+fn digits<T: Number>(input: &str, start: usize, radix: u32) -> Result<Digits, ParseError> {
+    let mut digits = Vec::new();
+    let mut after_digit = false;
+    let mut end = input.len();
+
+    let mut chars = input[start..].char_indices().peekable();
+    while let Some((offset, c)) = chars.next() {
+        let position = start + offset;
+
+        if c == '_' {
+            let before_digit = chars.peek().is_some_and(|&(_, next)| next.is_digit(radix));
+            if !after_digit || !before_digit {
+                return Err(ParseError::new::<T>(
+                    ErrorKind::MisplacedSeparator,
+                    input,
+                    position,
+                ));
+            }
+            after_digit = false;
+            continue;
+        }
+
+        match c.to_digit(radix) {
+            Some(digit) => {
+                digits.push((position, digit));
+                after_digit = true;
+            }
+            None => {
+                end = position;
+                break;
+            }
+        }
+    }
+
+    Ok(Digits { digits, end })
+}
+
+/// Splits off a leading `+` or `-`, returning whether it was `-` and where
+/// the rest starts.
+fn sign(input: &str) -> (bool, usize) {
+    match input.as_bytes().first() {
+        Some(b'-') => (true, 1),
+        Some(b'+') => (false, 1),
+        _ => (false, 0),
+    }
+}
+
+/// The character at `position`, for an error about it.
+fn invalid_digit<T: Number>(input: &str, position: usize, radix: u32) -> ParseError {
+    let found = input[position..].chars().next().unwrap_or_default();
+    ParseError::new::<T>(ErrorKind::InvalidDigit { found, radix }, input, position)
+}
+
+/// Integers build their value one digit at a time.
+trait Integer: Number {
+    const ZERO: Self;
+
+    /// `self * radix + digit`, or minus `digit` for negative numbers, so
+    /// that the minimum of a signed type can be reached.
+    fn step(self, radix: u32, digit: u32, negative: bool) -> Option<Self>;
+}
+
+fn parse_integer<T: Integer>(input: &str) -> Result<T, ParseError> {
+    let (negative, mut start) = sign(input);
+
+    let prefix = input.get(start..start + 2).map(str::to_ascii_lowercase);
+    let radix = match prefix.as_deref() {
+        Some("0x") => 16,
+        Some("0o") => 8,
+        Some("0b") => 2,
+        _ => 10,
+    };
+    if radix != 10 {
+        start += 2;
+    }
+
+    let Digits { digits, end } = digits::<T>(input, start, radix)?;
+    if end < input.len() {
+        return Err(invalid_digit::<T>(input, end, radix));
+    }
+    if digits.is_empty() {
+        return Err(ParseError::new::<T>(ErrorKind::Empty, input, start));
+    }
+
+    let mut value = T::ZERO;
+    for (position, digit) in digits {
+        value = value.step(radix, digit, negative).ok_or_else(|| {
+            let kind = if negative {
+                ErrorKind::Underflow {
+                    min: T::MIN.to_string(),
+                }
+            } else {
+                ErrorKind::Overflow {
+                    max: T::MAX.to_string(),
+                }
+            };
+            ParseError::new::<T>(kind, input, position)
+        })?;
+    }
+
+    Ok(value)
+}
+
+/// Checks the shape of a decimal float, `1_000.5e-3` say, before handing
+/// it to the standard library without its separators.
+fn parse_float<T: Number + FromStr + fmt::LowerExp>(
+    input: &str,
+    is_infinite: impl Fn(T) -> bool,
+) -> Result<T, ParseError> {
+    let (negative, start) = sign(input);
+
+    let whole = digits::<T>(input, start, 10)?;
+    let mut end = whole.end;
+    let mut has_digits = !whole.digits.is_empty();
+
+    if input[end..].starts_with('.') {
+        let fraction = digits::<T>(input, end + 1, 10)?;
+        has_digits |= !fraction.digits.is_empty();
+        end = fraction.end;
+    }
+    if !has_digits {
+        if end < input.len() {
+            return Err(invalid_digit::<T>(input, end, 10));
+        }
+        return Err(ParseError::new::<T>(ErrorKind::Empty, input, start));
+    }
+
+    if input[end..].starts_with(['e', 'E']) {
+        let (_, sign_length) = sign(&input[end + 1..]);
+        let exponent_start = end + 1 + sign_length;
+        let exponent = digits::<T>(input, exponent_start, 10)?;
+        if exponent.digits.is_empty() {
+            if exponent.end < input.len() {
+                return Err(invalid_digit::<T>(input, exponent.end, 10));
+            }
+            return Err(ParseError::new::<T>(
+                ErrorKind::Empty,
+                input,
+                exponent_start,
+            ));
+        }
+        end = exponent.end;
+    }
+    if end < input.len() {
+        return Err(invalid_digit::<T>(input, end, 10));
+    }
+
+    let value: T = input
+        .replace('_', "")
+        .parse()
+        .map_err(|_| invalid_digit::<T>(input, start, 10))?;
+
+    // Too big a float rounds to infinity rather than failing.
+    if is_infinite(value) {
+        let kind = if negative {
+            ErrorKind::Underflow {
+                min: format!("{:e}", T::MIN),
+            }
+        } else {
+            ErrorKind::Overflow {
+                max: format!("{:e}", T::MAX),
+            }
+        };
+        return Err(ParseError::new::<T>(kind, input, start));
+    }
+
+    Ok(value)
+}
+
+macro_rules! integer {
+    ($($t:ty),*) => {$(
+        impl Number for $t {
+            const NAME: &'static str = stringify!($t);
+            const MIN: $t = <$t>::MIN;
+            const MAX: $t = <$t>::MAX;
+
+            fn parse_number(input: &str) -> Result<$t, ParseError> {
+                parse_integer(input)
+            }
+        }
+
+        impl Integer for $t {
+            const ZERO: $t = 0;
+
+            fn step(self, radix: u32, digit: u32, negative: bool) -> Option<$t> {
+                // Every radix and digit fits in `u8`, and so in every type.
+                let radix = radix as $t;
+                let digit = digit as $t;
+                let shifted = self.checked_mul(radix)?;
+                if negative {
+                    shifted.checked_sub(digit)
+                } else {
+                    shifted.checked_add(digit)
+                }
+            }
+        }
+    )*};
+}
+
+macro_rules! float {
+    ($($t:ty),*) => {$(
+        impl Number for $t {
+            const NAME: &'static str = stringify!($t);
+            const MIN: $t = <$t>::MIN;
+            const MAX: $t = <$t>::MAX;
+
+            fn parse_number(input: &str) -> Result<$t, ParseError> {
+                parse_float(input, <$t>::is_infinite)
+            }
+        }
+    )*};
+}
+
+integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+float!(f32, f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kind<T: Number + fmt::Debug>(input: &str) -> (ErrorKind, usize) {
+        let err = parse::<T>(input).unwrap_err();
+        assert_eq!(err.input, input);
+        assert_eq!(err.type_name, T::NAME);
+        (err.kind, err.position)
+    }
+
+    fn invalid(found: char, radix: u32) -> ErrorKind {
+        ErrorKind::InvalidDigit { found, radix }
+    }
+
+    fn overflow(max: &str) -> ErrorKind {
+        ErrorKind::Overflow {
+            max: max.to_string(),
+        }
+    }
+
+    fn underflow(min: &str) -> ErrorKind {
+        ErrorKind::Underflow {
+            min: min.to_string(),
+        }
+    }
+
+    #[test]
+    fn parses_plain_and_signed_integers() {
+        assert_eq!(parse::<u8>("0"), Ok(0));
+        assert_eq!(parse::<u8>("+255"), Ok(255));
+        assert_eq!(parse::<u8>("-0"), Ok(0));
+        assert_eq!(parse::<i8>("-128"), Ok(i8::MIN));
+        assert_eq!(parse::<i64>("-9223372036854775808"), Ok(i64::MIN));
+        assert_eq!(parse::<u128>(&u128::MAX.to_string()), Ok(u128::MAX));
+    }
+
+    #[test]
+    fn needs_some_digits() {
+        assert_eq!(kind::<u8>(""), (ErrorKind::Empty, 0));
+        assert_eq!(kind::<i32>("-"), (ErrorKind::Empty, 1));
+        assert_eq!(kind::<u32>("0x"), (ErrorKind::Empty, 2));
+        assert_eq!(kind::<i32>("-0b"), (ErrorKind::Empty, 3));
+        assert_eq!(kind::<f64>("."), (ErrorKind::Empty, 0));
+        assert_eq!(kind::<f64>("1e"), (ErrorKind::Empty, 2));
+
+        assert_eq!(
+            parse::<u8>("").unwrap_err().to_string(),
+            "cannot parse an empty string as u8"
+        );
+        assert_eq!(
+            parse::<u32>("0x").unwrap_err().to_string(),
+            "\"0x\" has no digits at column 3"
+        );
+    }
+
+    #[test]
+    fn points_at_the_first_invalid_digit() {
+        assert_eq!(kind::<u8>("12a"), (invalid('a', 10), 2));
+        assert_eq!(kind::<u8>(" 1"), (invalid(' ', 10), 0));
+        assert_eq!(kind::<u8>("1 "), (invalid(' ', 10), 1));
+        assert_eq!(kind::<i8>("+-1"), (invalid('-', 10), 1));
+        assert_eq!(kind::<u32>("0x1g"), (invalid('g', 16), 3));
+        assert_eq!(kind::<u32>("0o18"), (invalid('8', 8), 3));
+        assert_eq!(kind::<u32>("0b102"), (invalid('2', 2), 4));
+        assert_eq!(kind::<f64>("1.5.2"), (invalid('.', 10), 3));
+        assert_eq!(kind::<f64>("1ex"), (invalid('x', 10), 2));
+        assert_eq!(kind::<f64>("inf"), (invalid('i', 10), 0));
+
+        let err = parse::<u16>("1_0é").unwrap_err();
+        assert_eq!((err.kind.clone(), err.position), (invalid('é', 10), 3));
+        assert_eq!(
+            err.to_string(),
+            "\"1_0é\" has 'é' at column 4, which is not a base 10 digit"
+        );
+    }
+
+    #[test]
+    fn counts_columns_in_characters() {
+        let err = parse::<u8>("éé").unwrap_err();
+        assert_eq!(err.position, 0);
+        assert_eq!(err.column(), 1);
+
+        // Parsing stops at the first character that is not ASCII, so build
+        // an error past one by hand.
+        let err = ParseError {
+            kind: ErrorKind::Empty,
+            input: String::from("ü1x"),
+            position: "ü1".len(),
+            type_name: "u8",
+        };
+        assert_eq!(err.column(), 3);
+    }
+
+    #[test]
+    fn overflows_at_the_digit_that_does_not_fit() {
+        assert_eq!(kind::<u8>("256"), (overflow("255"), 2));
+        assert_eq!(kind::<u8>("1000"), (overflow("255"), 3));
+        assert_eq!(kind::<i8>("128"), (overflow("127"), 2));
+        assert_eq!(kind::<u8>("0x1_00"), (overflow("255"), 5));
+        assert_eq!(
+            kind::<u64>("18446744073709551616"),
+            (overflow("18446744073709551615"), 19)
+        );
+
+        assert_eq!(
+            parse::<u8>("256").unwrap_err().to_string(),
+            "\"256\" is too large for u8, which goes up to 255; it overflows at column 3"
+        );
+    }
+
+    #[test]
+    fn underflows_below_the_minimum() {
+        assert_eq!(kind::<u8>("-1"), (underflow("0"), 1));
+        assert_eq!(kind::<i8>("-129"), (underflow("-128"), 3));
+        assert_eq!(kind::<i16>("-0x8001"), (underflow("-32768"), 6));
+
+        assert_eq!(
+            parse::<i8>("-129").unwrap_err().to_string(),
+            "\"-129\" is too small for i8, which goes down to -128; it underflows at column 4"
+        );
+    }
+
+    #[test]
+    fn reads_radix_prefixes_in_either_case() {
+        assert_eq!(parse::<u8>("0xff"), Ok(255));
+        assert_eq!(parse::<u8>("0XFF"), Ok(255));
+        assert_eq!(parse::<u16>("0o17"), Ok(15));
+        assert_eq!(parse::<u16>("0O17"), Ok(15));
+        assert_eq!(parse::<u8>("0b1111_0000"), Ok(240));
+        assert_eq!(parse::<u8>("0B1"), Ok(1));
+        assert_eq!(parse::<i8>("-0x80"), Ok(-128));
+        assert_eq!(parse::<i32>("+0b11"), Ok(3));
+        // Floats are decimal only.
+        assert_eq!(kind::<f64>("0x1"), (invalid('x', 10), 1));
+    }
+
+    #[test]
+    fn allows_separators_only_between_digits() {
+        assert_eq!(parse::<u32>("1_000"), Ok(1_000));
+        assert_eq!(parse::<u32>("1_0_0"), Ok(100));
+        assert_eq!(parse::<u32>("0xdead_beef"), Ok(0xdead_beef));
+        assert_eq!(parse::<f64>("1_000.5e-3"), Ok(1.0005));
+        assert_eq!(parse::<f64>("1_0.0_5"), Ok(10.05));
+
+        let misplaced = ErrorKind::MisplacedSeparator;
+        assert_eq!(kind::<u32>("_1"), (misplaced.clone(), 0));
+        assert_eq!(kind::<u32>("1_"), (misplaced.clone(), 1));
+        assert_eq!(kind::<u32>("1__0"), (misplaced.clone(), 1));
+        assert_eq!(kind::<i32>("-_1"), (misplaced.clone(), 1));
+        assert_eq!(kind::<u32>("0x_1"), (misplaced.clone(), 2));
+        assert_eq!(kind::<u32>("1_a"), (misplaced.clone(), 1));
+        assert_eq!(kind::<f64>("1_.5"), (misplaced.clone(), 1));
+        assert_eq!(kind::<f64>("1._5"), (misplaced.clone(), 2));
+        assert_eq!(kind::<f64>("1e_5"), (misplaced, 2));
+
+        assert_eq!(
+            parse::<u32>("1__0").unwrap_err().to_string(),
+            "\"1__0\" has a `_` at column 2 that is not between two digits"
+        );
+    }
+
+    #[test]
+    fn parses_floats_and_rejects_infinite_ones() {
+        assert_eq!(parse::<f64>("-2.5"), Ok(-2.5));
+        assert_eq!(parse::<f64>(".5"), Ok(0.5));
+        assert_eq!(parse::<f64>("5."), Ok(5.0));
+        assert_eq!(parse::<f32>("1E+3"), Ok(1_000.0));
+
+        assert_eq!(
+            kind::<f64>("1e400"),
+            (overflow("1.7976931348623157e308"), 0)
+        );
+        assert_eq!(
+            kind::<f64>("-1e400"),
+            (underflow("-1.7976931348623157e308"), 1)
+        );
+        assert_eq!(kind::<f32>("1e39"), (overflow("3.4028235e38"), 0));
+    }
+}
@@ -0,0 +1,141 @@
+use shadowing::{analyze, Analysis, Position};
+use std::io::{self, Read};
+use std::{env, fs, process};
+
+/// One line of the report, sorted by where it happens in the source.
+enum Event {
+    Binding(usize),
+    Use(usize),
+}
+
+fn main() {
+    let paths: Vec<String> = env::args().skip(1).collect();
+    if paths.iter().any(|path| path.starts_with("--")) {
+        eprintln!("Usage: scopes [FILE]...");
+        eprintln!("Reads stdin without a FILE, such as: scopes ../variables/src/main.rs");
+        process::exit(2);
+    }
+
+    let sources = if paths.is_empty() {
+        let mut source = String::new();
+        if let Err(err) = io::stdin().read_to_string(&mut source) {
+            eprintln!("Problem reading stdin: {err}");
+            process::exit(1);
+        }
+        vec![(String::from("stdin"), source)]
+    } else {
+        paths
+            .into_iter()
+            .map(|path| {
+                let source = fs::read_to_string(&path).unwrap_or_else(|err| {
+                    eprintln!("Problem reading {path}: {err}");
+                    process::exit(1);
+                });
+                (path, source)
+            })
+            .collect()
+    };
+
+    let mut failed = false;
+    for (index, (name, source)) in sources.iter().enumerate() {
+        if index > 0 {
+            println!();
+        }
+
+        match analyze(source) {
+            Ok(analysis) => {
+                println!("{name}");
+                print_report(&analysis);
+                failed |= analysis.immutable_assignments().next().is_some();
+            }
+            Err(err) => {
+                eprintln!("Problem parsing {name}: {err}");
+                failed = true;
+            }
+        }
+    }
+
+    if failed {
+        process::exit(1);
+    }
+}
+
+/// `x #2`, the way every line of the report names a binding.
+fn label(analysis: &Analysis, index: usize) -> String {
+    format!("{} #{}", analysis.bindings[index].name, index + 1)
+}
+
+fn print_report(analysis: &Analysis) {
+    let mut events: Vec<(Position, Event)> = Vec::new();
+    for (index, binding) in analysis.bindings.iter().enumerate() {
+        events.push((binding.position, Event::Binding(index)));
+    }
+    for (index, found) in analysis.uses.iter().enumerate() {
+        // Uses of functions, constants and the like are not interesting.
+        if found.binding.is_some() {
+            events.push((found.position, Event::Use(index)));
+        }
+    }
+    events.sort_by_key(|(position, _)| *position);
+
+    for (position, event) in &events {
+        let line = match *event {
+            Event::Binding(index) => {
+                let binding = &analysis.bindings[index];
+                let mut line = format!(
+                    "{} {}{} in depth {}",
+                    binding.kind,
+                    if binding.mutable { "mut " } else { "" },
+                    label(analysis, index),
+                    binding.depth
+                );
+                if let Some(hidden) = binding.shadows {
+                    line += &format!(", shadows #{}", hidden + 1);
+                }
+                if !binding.shadowed_by.is_empty() {
+                    let later: Vec<String> = binding
+                        .shadowed_by
+                        .iter()
+                        .map(|later| format!("#{}", later + 1))
+                        .collect();
+                    line += &format!(", shadowed by {}", later.join(" and "));
+                }
+                line
+            }
+            Event::Use(index) => {
+                let found = &analysis.uses[index];
+                let binding = found.binding.expect("only resolved uses are listed");
+                let verb = if found.assignment {
+                    "assigns to"
+                } else {
+                    "refers to"
+                };
+                let mut line = format!(
+                    "{} {verb} #{}, bound at {}",
+                    found.name,
+                    binding + 1,
+                    analysis.bindings[binding].position
+                );
+                if found.assignment && !analysis.bindings[binding].mutable {
+                    line += ", which is not mutable";
+                }
+                line
+            }
+        };
+        println!("  {:<7} {line}", position.to_string());
+    }
+
+    let shadowed = analysis
+        .bindings
+        .iter()
+        .filter(|binding| !binding.shadowed_by.is_empty())
+        .count();
+    let mutable = analysis
+        .bindings
+        .iter()
+        .filter(|binding| binding.mutable)
+        .count();
+    let count = analysis.bindings.len();
+    let plural = if count == 1 { "" } else { "s" };
+    println!("{count} binding{plural}: {shadowed} shadowed, {mutable} mutable");
+}
@@ -0,0 +1,9 @@
+//! Works out which binding each name in a listing refers to, for this
+//! listing and the `variables` one next to it. The `scopes` binary reads
+//! them from disk, as in `scopes src/main.rs ../variables/src/main.rs`,
+//! so the listings stay as the book prints them and `variables` needs no
+//! dependencies of its own.
+
+pub mod scope;
+
+pub use crate::scope::{analyze, Analysis, Binding, BindingKind, ParseError, Position, Use};
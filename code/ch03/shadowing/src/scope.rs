@@ -0,0 +1,772 @@
+use proc_macro2::{LineColumn, Span};
+use std::error::Error;
+use std::fmt;
+use syn::punctuated::Punctuated;
+use syn::visit::{self, Visit};
+use syn::{BinOp, Block, Expr, ExprLit, Lit, Macro, Pat, Token};
+
+/// Macros whose first string literal may capture variables, as in
+/// `println!("{x}")`.
+const FORMAT_MACROS: [&str; 11] = [
+    "print",
+    "println",
+    "eprint",
+    "eprintln",
+    "format",
+    "format_args",
+    "write",
+    "writeln",
+    "panic",
+    "todo",
+    "unimplemented",
+];
+
+/// A place in the source. Both numbers start at 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl From<LineColumn> for Position {
+    /// `LineColumn` counts columns from 0.
+    fn from(location: LineColumn) -> Position {
+        Position {
+            line: location.line,
+            column: location.column + 1,
+        }
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// What introduced a binding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BindingKind {
+    /// A `let` statement, with or without an `else`.
+    Let,
+    /// A parameter of a function or method.
+    Param,
+    ClosureParam,
+    IfLet,
+    WhileLet,
+    For,
+    MatchArm,
+}
+
+impl fmt::Display for BindingKind {
+    /// The code that makes the binding, such as `if let`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            BindingKind::Let => "let",
+            BindingKind::Param => "parameter",
+            BindingKind::ClosureParam => "closure parameter",
+            BindingKind::IfLet => "if let",
+            BindingKind::WhileLet => "while let",
+            BindingKind::For => "for",
+            BindingKind::MatchArm => "match arm",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// A `let`, a function or closure parameter, or a name bound by a `match`
+/// arm, `if let`, `while let` or `for` pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Binding {
+    pub name: String,
+    pub kind: BindingKind,
+    pub position: Position,
+    /// 1 for a function's parameters and outermost block, 2 for a block
+    /// inside that, and so on.
+    pub depth: usize,
+    pub mutable: bool,
+    /// The index of the binding this one hides, if another with the same
+    /// name was visible.
+    pub shadows: Option<usize>,
+    /// The indexes of later bindings that hide this one.
+    pub shadowed_by: Vec<usize>,
+}
+
+/// A name read or assigned to, and the binding it refers to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Use {
+    pub name: String,
+    pub position: Position,
+    /// `None` for names not bound inside the snippet, such as functions.
+    pub binding: Option<usize>,
+    /// `x = ...` or `x += ...` rather than a read.
+    pub assignment: bool,
+}
+
+/// Every binding and every use of one, in source order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Analysis {
+    pub bindings: Vec<Binding>,
+    pub uses: Vec<Use>,
+}
+
+impl Analysis {
+    /// Puts everything in source order. Names are resolved in the order
+    /// they come into scope, which puts a closure's parameters before the
+    /// `let` it is assigned to, so the indexes are renumbered to match.
+    fn sort(&mut self) {
+        let mut order: Vec<usize> = (0..self.bindings.len()).collect();
+        order.sort_by_key(|&index| self.bindings[index].position);
+
+        let mut renumbered = vec![0; order.len()];
+        for (new, &old) in order.iter().enumerate() {
+            renumbered[old] = new;
+        }
+
+        let mut bindings: Vec<Option<Binding>> = self.bindings.drain(..).map(Some).collect();
+        for &old in &order {
+            let mut binding = bindings[old].take().expect("each binding moves once");
+            binding.shadows = binding.shadows.map(|hidden| renumbered[hidden]);
+            for later in &mut binding.shadowed_by {
+                *later = renumbered[*later];
+            }
+            binding.shadowed_by.sort_unstable();
+            self.bindings.push(binding);
+        }
+
+        for found in &mut self.uses {
+            found.binding = found.binding.map(|index| renumbered[index]);
+        }
+        self.uses.sort_by_key(|found| found.position);
+    }
+
+    /// Assignments to bindings that are not `mut`, which do not compile.
+    pub fn immutable_assignments(&self) -> impl Iterator<Item = &Use> {
+        self.uses.iter().filter(|found| {
+            found.assignment
+                && found
+                    .binding
+                    .is_some_and(|index| !self.bindings[index].mutable)
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub position: Position,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.position, self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses `source` and resolves every use of a local name.
+///
+/// `source` is either a whole file, like a listing's `main.rs`, or just
+/// the statements of a function body.
+pub fn analyze(source: &str) -> Result<Analysis, ParseError> {
+    let mut analyzer = Analyzer::default();
+
+    match syn::parse_file(source) {
+        Ok(file) => analyzer.visit_file(&file),
+        Err(file_err) => {
+            // Put the statements on their own lines, so only the line
+            // numbers need fixing afterwards.
+            let wrapped = format!("{{\n{source}\n}}");
+            let block: Block = syn::parse_str(&wrapped).map_err(|_| ParseError {
+                position: file_err.span().start().into(),
+                message: file_err.to_string(),
+            })?;
+
+            analyzer.visit_block(&block);
+            analyzer.unwrap_lines();
+        }
+    }
+
+    analyzer.analysis.sort();
+    Ok(analyzer.analysis)
+}
+
+#[derive(Default)]
+struct Analyzer {
+    analysis: Analysis,
+    /// The bindings visible in each open scope, innermost last.
+    scopes: Vec<Vec<usize>>,
+}
+
+impl Analyzer {
+    fn push(&mut self) {
+        self.scopes.push(Vec::new());
+    }
+
+    fn pop(&mut self) {
+        self.scopes.pop();
+    }
+
+    /// The innermost binding of `name` still in scope.
+    fn lookup(&self, name: &str) -> Option<usize> {
+        self.scopes
+            .iter()
+            .rev()
+            .flat_map(|scope| scope.iter().rev())
+            .copied()
+            .find(|&index| self.analysis.bindings[index].name == name)
+    }
+
+    fn declare(&mut self, name: String, kind: BindingKind, span: Span, mutable: bool) {
+        if self.scopes.is_empty() {
+            self.push();
+        }
+
+        let index = self.analysis.bindings.len();
+        let shadows = self.lookup(&name);
+        if let Some(hidden) = shadows {
+            self.analysis.bindings[hidden].shadowed_by.push(index);
+        }
+
+        self.analysis.bindings.push(Binding {
+            name,
+            kind,
+            position: span.start().into(),
+            depth: self.scopes.len(),
+            mutable,
+            shadows,
+            shadowed_by: Vec::new(),
+        });
+        self.scopes.last_mut().unwrap().push(index);
+    }
+
+    fn refer(&mut self, name: String, position: Position, assignment: bool) {
+        let binding = self.lookup(&name);
+        self.analysis.uses.push(Use {
+            name,
+            position,
+            binding,
+            assignment,
+        });
+    }
+
+    /// Declares every name `pat` binds.
+    fn declare_pattern(&mut self, pat: &Pat, kind: BindingKind) {
+        match pat {
+            Pat::Ident(ident) => {
+                // `None` or `MAX` in a pattern names a variant or a
+                // constant; syn cannot tell them apart from bindings.
+                let name = ident.ident.to_string();
+                if name.starts_with(char::is_uppercase) && ident.subpat.is_none() {
+                    return;
+                }
+
+                self.declare(name, kind, ident.ident.span(), ident.mutability.is_some());
+                if let Some((_, subpat)) = &ident.subpat {
+                    self.declare_pattern(subpat, kind);
+                }
+            }
+            // Every alternative binds the same names; the first will do.
+            Pat::Or(or) => {
+                if let Some(first) = or.cases.first() {
+                    self.declare_pattern(first, kind);
+                }
+            }
+            Pat::Paren(paren) => self.declare_pattern(&paren.pat, kind),
+            Pat::Reference(reference) => self.declare_pattern(&reference.pat, kind),
+            Pat::Slice(slice) => slice
+                .elems
+                .iter()
+                .for_each(|pat| self.declare_pattern(pat, kind)),
+            Pat::Struct(strukt) => strukt
+                .fields
+                .iter()
+                .for_each(|field| self.declare_pattern(&field.pat, kind)),
+            Pat::Tuple(tuple) => tuple
+                .elems
+                .iter()
+                .for_each(|pat| self.declare_pattern(pat, kind)),
+            Pat::TupleStruct(tuple) => tuple
+                .elems
+                .iter()
+                .for_each(|pat| self.declare_pattern(pat, kind)),
+            Pat::Type(typed) => self.declare_pattern(&typed.pat, kind),
+            _ => {}
+        }
+    }
+
+    /// Visits an `if` or `while` condition, declaring the names its `let`s
+    /// bind, including each one of a chain like `let a = x && let b = y`.
+    fn condition(&mut self, cond: &Expr, kind: BindingKind) {
+        match cond {
+            Expr::Let(expr) => {
+                self.visit_expr(&expr.expr);
+                self.declare_pattern(&expr.pat, kind);
+            }
+            Expr::Binary(expr) if matches!(expr.op, BinOp::And(_)) => {
+                self.condition(&expr.left, kind);
+                self.condition(&expr.right, kind);
+            }
+            _ => self.visit_expr(cond),
+        }
+    }
+
+    /// Records the variables captured by a format string, such as the `x`
+    /// in `"{x}"` or `"{x:>5}"`.
+    fn format_captures(&mut self, literal: &ExprLit, named: &[String]) {
+        let Lit::Str(text) = &literal.lit else {
+            return;
+        };
+
+        // Scan the literal as written, so escapes do not shift columns.
+        let raw = text.token().to_string();
+        let start: Position = text.span().start().into();
+        let mut chars = raw.char_indices().peekable();
+
+        while let Some((_, c)) = chars.next() {
+            if c != '{' {
+                continue;
+            }
+            if chars.next_if(|&(_, next)| next == '{').is_some() {
+                continue;
+            }
+
+            let mut name = String::new();
+            let offset = chars.peek().map_or(raw.len(), |&(at, _)| at);
+            while let Some((_, c)) = chars.next_if(|&(_, c)| c == '_' || c.is_alphanumeric()) {
+                name.push(c);
+            }
+
+            let is_identifier = name.starts_with(|c: char| c == '_' || c.is_alphabetic());
+            // A lone `_` is not a variable.
+            if is_identifier && name != "_" && !named.contains(&name) {
+                self.refer(name, offset_position(start, &raw[..offset]), false);
+            }
+        }
+    }
+
+    /// The snippet was parsed inside an extra `{` line; take it back out.
+    fn unwrap_lines(&mut self) {
+        let positions = self
+            .analysis
+            .bindings
+            .iter_mut()
+            .map(|binding| &mut binding.position)
+            .chain(
+                self.analysis
+                    .uses
+                    .iter_mut()
+                    .map(|found| &mut found.position),
+            );
+
+        for position in positions {
+            position.line -= 1;
+        }
+    }
+}
+
+/// A bare name such as `x`, as opposed to `std::f64::consts::PI`.
+fn local_name(expr: &Expr) -> Option<(String, Position)> {
+    match expr {
+        Expr::Path(path) => path_name(path),
+        _ => None,
+    }
+}
+
+fn path_name(path: &syn::ExprPath) -> Option<(String, Position)> {
+    if path.qself.is_some() {
+        return None;
+    }
+
+    let ident = path.path.get_ident()?;
+    Some((ident.to_string(), ident.span().start().into()))
+}
+
+/// Where the text after `before` starts, for a literal that starts at
+/// `start` and may span several lines.
+fn offset_position(start: Position, before: &str) -> Position {
+    match before.rsplit_once('\n') {
+        Some((lines, last)) => Position {
+            line: start.line + lines.matches('\n').count() + 1,
+            column: last.chars().count() + 1,
+        },
+        None => Position {
+            line: start.line,
+            column: start.column + before.chars().count(),
+        },
+    }
+}
+
+fn is_compound_assignment(op: &BinOp) -> bool {
+    matches!(
+        op,
+        BinOp::AddAssign(_)
+            | BinOp::SubAssign(_)
+            | BinOp::MulAssign(_)
+            | BinOp::DivAssign(_)
+            | BinOp::RemAssign(_)
+            | BinOp::BitXorAssign(_)
+            | BinOp::BitAndAssign(_)
+            | BinOp::BitOrAssign(_)
+            | BinOp::ShlAssign(_)
+            | BinOp::ShrAssign(_)
+    )
+}
+
+impl<'ast> Visit<'ast> for Analyzer {
+    /// Every `{ ... }` is a scope. Bodies that need one for a pattern's
+    /// names too, like an `if let` body, open it themselves instead.
+    fn visit_block(&mut self, block: &'ast Block) {
+        self.push();
+        visit::visit_block(self, block);
+        self.pop();
+    }
+
+    /// The initializer runs before the new names exist, which is what makes
+    /// `let x = x + 1;` refer to the previous `x`.
+    fn visit_local(&mut self, local: &'ast syn::Local) {
+        if let Some(init) = &local.init {
+            self.visit_expr(&init.expr);
+            if let Some((_, diverge)) = &init.diverge {
+                self.visit_expr(diverge);
+            }
+        }
+        self.declare_pattern(&local.pat, BindingKind::Let);
+    }
+
+    /// A nested function cannot see the enclosing function's locals.
+    fn visit_item_fn(&mut self, item: &'ast syn::ItemFn) {
+        let outer = std::mem::take(&mut self.scopes);
+
+        self.push();
+        for input in &item.sig.inputs {
+            if let syn::FnArg::Typed(typed) = input {
+                self.declare_pattern(&typed.pat, BindingKind::Param);
+            }
+        }
+        // The body shares the parameters' scope, as `let` in it can
+        // shadow a parameter without being any deeper.
+        visit::visit_block(self, &item.block);
+
+        self.scopes = outer;
+    }
+
+    fn visit_impl_item_fn(&mut self, item: &'ast syn::ImplItemFn) {
+        let outer = std::mem::take(&mut self.scopes);
+
+        self.push();
+        for input in &item.sig.inputs {
+            if let syn::FnArg::Typed(typed) = input {
+                self.declare_pattern(&typed.pat, BindingKind::Param);
+            }
+        }
+        visit::visit_block(self, &item.block);
+
+        self.scopes = outer;
+    }
+
+    fn visit_expr_closure(&mut self, closure: &'ast syn::ExprClosure) {
+        self.push();
+        for input in &closure.inputs {
+            self.declare_pattern(input, BindingKind::ClosureParam);
+        }
+        self.visit_expr(&closure.body);
+        self.pop();
+    }
+
+    /// Names from `if let` are only visible in the `then` branch.
+    fn visit_expr_if(&mut self, expr: &'ast syn::ExprIf) {
+        self.push();
+        self.condition(&expr.cond, BindingKind::IfLet);
+        visit::visit_block(self, &expr.then_branch);
+        self.pop();
+
+        if let Some((_, otherwise)) = &expr.else_branch {
+            self.visit_expr(otherwise);
+        }
+    }
+
+    fn visit_expr_while(&mut self, expr: &'ast syn::ExprWhile) {
+        self.push();
+        self.condition(&expr.cond, BindingKind::WhileLet);
+        visit::visit_block(self, &expr.body);
+        self.pop();
+    }
+
+    /// Conditions handle their own `let`s; one anywhere else does not
+    /// compile, but is treated like an `if let`.
+    fn visit_expr_let(&mut self, expr: &'ast syn::ExprLet) {
+        self.visit_expr(&expr.expr);
+        self.declare_pattern(&expr.pat, BindingKind::IfLet);
+    }
+
+    fn visit_expr_for_loop(&mut self, expr: &'ast syn::ExprForLoop) {
+        self.visit_expr(&expr.expr);
+
+        self.push();
+        self.declare_pattern(&expr.pat, BindingKind::For);
+        visit::visit_block(self, &expr.body);
+        self.pop();
+    }
+
+    fn visit_arm(&mut self, arm: &'ast syn::Arm) {
+        self.push();
+        self.declare_pattern(&arm.pat, BindingKind::MatchArm);
+        if let Some((_, guard)) = &arm.guard {
+            self.visit_expr(guard);
+        }
+        self.visit_expr(&arm.body);
+        self.pop();
+    }
+
+    fn visit_expr_path(&mut self, expr: &'ast syn::ExprPath) {
+        if let Some((name, position)) = path_name(expr) {
+            self.refer(name, position, false);
+        }
+    }
+
+    fn visit_expr_assign(&mut self, expr: &'ast syn::ExprAssign) {
+        self.visit_expr(&expr.right);
+
+        match local_name(&expr.left) {
+            Some((name, position)) => self.refer(name, position, true),
+            None => self.visit_expr(&expr.left),
+        }
+    }
+
+    fn visit_expr_binary(&mut self, expr: &'ast syn::ExprBinary) {
+        if !is_compound_assignment(&expr.op) {
+            return visit::visit_expr_binary(self, expr);
+        }
+
+        self.visit_expr(&expr.right);
+        match local_name(&expr.left) {
+            Some((name, position)) => self.refer(name, position, true),
+            None => self.visit_expr(&expr.left),
+        }
+    }
+
+    /// Macro arguments are only tokens to syn; format macros and anything
+    /// else shaped like a call are parsed as expressions.
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        let Ok(args) = mac.parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated) else {
+            return;
+        };
+
+        // `println!("{x}", x = 5)` names an argument, not a variable.
+        let mut named = Vec::new();
+        for arg in &args {
+            if let Expr::Assign(assign) = arg {
+                if let Some((name, _)) = local_name(&assign.left) {
+                    named.push(name);
+                }
+            }
+        }
+
+        let is_format = mac
+            .path
+            .get_ident()
+            .is_some_and(|name| FORMAT_MACROS.contains(&name.to_string().as_str()));
+        let mut seen_format_string = false;
+
+        for arg in &args {
+            match arg {
+                Expr::Lit(literal) if is_format && !seen_format_string => {
+                    if matches!(literal.lit, Lit::Str(_)) {
+                        seen_format_string = true;
+                        self.format_captures(literal, &named);
+                    }
+                }
+                Expr::Assign(assign) if local_name(&assign.left).is_some() => {
+                    self.visit_expr(&assign.right)
+                }
+                _ => self.visit_expr(arg),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(line: usize, column: usize) -> Position {
+        Position { line, column }
+    }
+
+    /// Each use as `(name, where, the index of its binding, assignment)`.
+    fn uses(analysis: &Analysis) -> Vec<(&str, Position, Option<usize>, bool)> {
+        analysis
+            .uses
+            .iter()
+            .map(|found| {
+                let name = found.name.as_str();
+                (name, found.position, found.binding, found.assignment)
+            })
+            .collect()
+    }
+
+    fn kinds(analysis: &Analysis) -> Vec<(&str, BindingKind)> {
+        analysis
+            .bindings
+            .iter()
+            .map(|binding| (binding.name.as_str(), binding.kind))
+            .collect()
+    }
+
+    #[test]
+    fn resolves_the_shadowing_listing() {
+        let analysis = analyze(include_str!("main.rs")).unwrap();
+
+        let bindings: Vec<_> = analysis
+            .bindings
+            .iter()
+            .map(|binding| {
+                let shadowed_by = binding.shadowed_by.as_slice();
+                (
+                    binding.position,
+                    binding.depth,
+                    binding.shadows,
+                    shadowed_by,
+                )
+            })
+            .collect();
+        assert_eq!(
+            bindings,
+            [
+                (at(2, 9), 1, None, &[1][..]),
+                (at(3, 9), 1, Some(0), &[2][..]),
+                (at(6, 13), 2, Some(1), &[][..]),
+            ]
+        );
+        assert!(analysis.bindings.iter().all(|binding| !binding.mutable));
+        assert!(analysis
+            .bindings
+            .iter()
+            .all(|binding| binding.kind == BindingKind::Let));
+
+        assert_eq!(
+            uses(&analysis),
+            [
+                ("x", at(3, 13), Some(0), false),
+                ("x", at(6, 17), Some(1), false),
+                ("x", at(7, 58), Some(2), false),
+                ("x", at(10, 35), Some(1), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn resolves_the_variables_listing() {
+        let analysis = analyze(include_str!("../../variables/src/main.rs")).unwrap();
+
+        assert_eq!(analysis.bindings.len(), 1);
+        let x = &analysis.bindings[0];
+        assert_eq!((x.position, x.depth, x.mutable), (at(2, 13), 1, true));
+        assert_eq!((x.shadows, x.shadowed_by.len()), (None, 0));
+
+        assert_eq!(
+            uses(&analysis),
+            [
+                ("x", at(3, 35), Some(0), false),
+                ("x", at(4, 5), Some(0), true),
+                ("x", at(5, 35), Some(0), false),
+            ]
+        );
+        assert_eq!(analysis.immutable_assignments().count(), 0);
+    }
+
+    #[test]
+    fn catches_assignments_to_immutable_bindings() {
+        let analysis = analyze("let x = 5;\nx = 6;\nlet mut y = 1;\ny += x;").unwrap();
+        let wrong: Vec<Position> = analysis
+            .immutable_assignments()
+            .map(|found| found.position)
+            .collect();
+
+        // Snippets are numbered as written, without the wrapping block.
+        assert_eq!(wrong, [at(2, 1)]);
+    }
+
+    #[test]
+    fn tells_every_kind_of_binding_apart() {
+        let source = "
+fn f(mut n: u32) -> u32 {
+    let add = |a, b| a + b;
+    if let Some(m) = Some(n) { n = m; }
+    while let Some(t) = None::<u32> { n += t; }
+    for (i, _) in [(1, 2)] { n = add(n, i); }
+    match n { 0 => 0, k => k }
+}";
+        let analysis = analyze(source).unwrap();
+
+        assert_eq!(
+            kinds(&analysis),
+            [
+                ("n", BindingKind::Param),
+                ("add", BindingKind::Let),
+                ("a", BindingKind::ClosureParam),
+                ("b", BindingKind::ClosureParam),
+                ("m", BindingKind::IfLet),
+                ("t", BindingKind::WhileLet),
+                ("i", BindingKind::For),
+                ("k", BindingKind::MatchArm),
+            ]
+        );
+        assert!(analysis.bindings[0].mutable);
+        assert_eq!(analysis.immutable_assignments().count(), 0);
+    }
+
+    #[test]
+    fn scopes_pattern_bindings_to_their_bodies() {
+        let source = "
+let x = 1;
+if let Some(x) = Some(x) { x; }
+x;
+let v = |x: i32| x;
+x;";
+        let analysis = analyze(source).unwrap();
+
+        assert_eq!(
+            kinds(&analysis),
+            [
+                ("x", BindingKind::Let),
+                ("x", BindingKind::IfLet),
+                ("v", BindingKind::Let),
+                ("x", BindingKind::ClosureParam),
+            ]
+        );
+        // `Some` is a use too, of nothing bound here.
+        let resolved: Vec<Option<usize>> = analysis
+            .uses
+            .iter()
+            .filter(|found| found.name == "x")
+            .map(|found| found.binding)
+            .collect();
+        assert_eq!(resolved, [Some(0), Some(1), Some(0), Some(3), Some(0)]);
+        assert_eq!(analysis.bindings[0].shadowed_by, [1, 3]);
+        assert_eq!(analysis.bindings[3].shadows, Some(0));
+    }
+
+    #[test]
+    fn reads_names_captured_by_format_strings() {
+        let analysis =
+            analyze("let x = 1;\nlet y = 2;\nprintln!(\"{x:>5} {{y}} {z}\", z = y);").unwrap();
+
+        assert_eq!(
+            uses(&analysis),
+            [
+                ("x", at(3, 12), Some(0), false),
+                ("y", at(3, 34), Some(1), false)
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_where_parsing_failed() {
+        let err = analyze("let = ;").unwrap_err();
+        assert_eq!(err.position.line, 1);
+        assert!(!err.message.is_empty());
+    }
+}
@@ -1,18 +1,61 @@
+use std::ops::Range;
+
 fn main() {
     let string = String::from("hello, world!");
     let first_word = first_word(&string);
-    
-    println!("{first_word}");
+
+    println!("{}", &string[first_word]);
 }
 
-fn first_word(s: &String) -> usize {
-    let bytes = s.as_bytes();
+/// The bytes of `s` that make up its first word, empty if it has none.
+///
+/// A word starts at a letter or digit and runs up to any Unicode
+/// whitespace, less the punctuation at its end, so the first word of
+/// `"hello, world!"` is `hello`. Like the `usize` this used to return,
+/// the range knows nothing about `s`, and is wrong as soon as `s` changes.
+fn first_word(s: &str) -> Range<usize> {
+    let Some(start) = s.find(char::is_alphanumeric) else {
+        return 0..0;
+    };
+    let rest = &s[start..];
+    let length = rest.find(char::is_whitespace).unwrap_or(rest.len());
+    let word = rest[..length].trim_end_matches(|c: char| !c.is_alphanumeric());
+
+    start..start + word.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(s: &str) -> &str {
+        &s[first_word(s)]
+    }
 
-    for (i, &item) in bytes.iter().enumerate() {
-        if item == b' ' {
-            return i;
-        }
+    #[test]
+    fn stops_at_any_unicode_whitespace() {
+        assert_eq!(word("hello world"), "hello");
+        assert_eq!(word("tab\tseparated"), "tab");
+        assert_eq!(word("no\u{a0}break"), "no");
+        assert_eq!(word("회의\u{3000}메모"), "회의");
     }
 
-    s.len()
-}
\ No newline at end of file
+    #[test]
+    fn skips_punctuation_around_the_word() {
+        assert_eq!(word("hello, world!"), "hello");
+        assert_eq!(word("  \"quoted\" word"), "quoted");
+        assert_eq!(word("don't stop"), "don't");
+    }
+
+    #[test]
+    fn is_empty_without_a_word() {
+        assert_eq!(first_word(""), 0..0);
+        assert_eq!(first_word(" \t\n"), 0..0);
+        assert_eq!(first_word("..."), 0..0);
+    }
+
+    #[test]
+    fn counts_bytes_not_characters() {
+        assert_eq!(first_word("\u{a0}é!"), 2..4);
+    }
+}
@@ -0,0 +1,3 @@
+pub mod words;
+
+pub use crate::words::{first_word, last_word, nth_word, words, Word, Words};
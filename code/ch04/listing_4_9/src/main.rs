@@ -1,33 +1,41 @@
-fn first_word(s: &str) -> &str {
-    let bytes = s.as_bytes();
-
-    for (i, &item) in bytes.iter().enumerate() {
-        if item == b' ' {
-            return &s[0..i];
-        }
-    }
-
-    &s[..]
-}
+use listing_4_9::{first_word, last_word, nth_word, words};
 
+// The slices below are the point of the listing, redundant or not.
+#[allow(clippy::redundant_slicing)]
 fn main() {
     let my_string = String::from("hello world");
 
     // `first_word` works on slices of `String`s, whether partial or whole.
     let word = first_word(&my_string[0..6]);
+    println!("{word:?}");
     let word = first_word(&my_string[..]);
+    println!("{word:?}");
 
     // `first_word` also works on references to `String`s,
     // which are equivalent to whole slices of `String`s.
     let word = first_word(&my_string);
+    println!("{word:?}");
 
     let my_string_literal = "hello world";
 
     // `first_word` works on slices of string literals, whether partial or whole.
     let word = first_word(&my_string_literal[0..6]);
+    println!("{word:?}");
     let word = first_word(&my_string_literal[..]);
+    println!("{word:?}");
 
     // Because string literals *are* string slices already,
     // this works too, without the slice syntax!
     let word = first_word(my_string_literal);
-}
\ No newline at end of file
+    println!("{word:?}");
+
+    // Words are split on any Unicode whitespace, not just b' ': here a
+    // tab, a non-breaking space and an ideographic space.
+    let notes = "회의\t메모:\u{a0}다음 주\u{3000}월요일";
+    println!("{:?} … {:?}", first_word(notes), last_word(notes));
+    println!("third: {:?}", nth_word(notes, 2));
+
+    for word in words(notes) {
+        println!("{:?} at bytes {:?}", word.text, word.span());
+    }
+}
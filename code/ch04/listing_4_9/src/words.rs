@@ -0,0 +1,148 @@
+use std::ops::Range;
+use unicode_segmentation::{UWordBoundIndices, UnicodeSegmentation};
+
+/// One word of a string: a slice of it, and where that slice starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Word<'a> {
+    pub text: &'a str,
+    /// The byte offset of `text` in the string it came from.
+    pub start: usize,
+}
+
+impl Word<'_> {
+    /// The bytes `text` covers in the string it came from.
+    pub fn span(&self) -> Range<usize> {
+        self.start..self.start + self.text.len()
+    }
+}
+
+/// The words of a string, from either end, without copying any of it.
+///
+/// Words are split by the Unicode word-boundary rules (UAX #29), so tabs,
+/// newlines, non-breaking spaces and the ideographic space all separate
+/// words, and punctuation is left out: the words of `"hello, world!"`
+/// are `hello` and `world`. Text without spaces between words, such as
+/// Chinese or Japanese, comes out one ideograph at a time; Korean, which
+/// does use spaces, comes out in whole words.
+#[derive(Debug, Clone)]
+pub struct Words<'a> {
+    segments: UWordBoundIndices<'a>,
+}
+
+pub fn words(s: &str) -> Words<'_> {
+    Words {
+        segments: s.split_word_bound_indices(),
+    }
+}
+
+/// A segment is a word if it has a letter or digit in it; the others are
+/// runs of whitespace or single punctuation marks.
+fn to_word((start, text): (usize, &str)) -> Option<Word<'_>> {
+    if text.chars().any(char::is_alphanumeric) {
+        Some(Word { text, start })
+    } else {
+        None
+    }
+}
+
+impl<'a> Iterator for Words<'a> {
+    type Item = Word<'a>;
+
+    fn next(&mut self) -> Option<Word<'a>> {
+        self.segments.by_ref().find_map(to_word)
+    }
+}
+
+impl<'a> DoubleEndedIterator for Words<'a> {
+    fn next_back(&mut self) -> Option<Word<'a>> {
+        self.segments.by_ref().rev().find_map(to_word)
+    }
+}
+
+pub fn first_word(s: &str) -> Option<&str> {
+    words(s).next().map(|word| word.text)
+}
+
+/// Walks the string from the end, so the words before are never split.
+pub fn last_word(s: &str) -> Option<&str> {
+    words(s).next_back().map(|word| word.text)
+}
+
+/// The word at index `n`, counting from 0.
+pub fn nth_word(s: &str, n: usize) -> Option<&str> {
+    words(s).nth(n).map(|word| word.text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(s: &str) -> Vec<&str> {
+        words(s).map(|word| word.text).collect()
+    }
+
+    #[test]
+    fn splits_on_any_unicode_whitespace() {
+        assert_eq!(texts("one\ttwo\nthree"), ["one", "two", "three"]);
+        assert_eq!(texts("one\r\ntwo"), ["one", "two"]);
+        assert_eq!(texts("no\u{a0}break"), ["no", "break"]);
+        assert_eq!(texts("ideographic\u{3000}space"), ["ideographic", "space"]);
+    }
+
+    #[test]
+    fn leaves_out_punctuation() {
+        assert_eq!(texts("hello, world!"), ["hello", "world"]);
+        assert_eq!(texts("don't stop"), ["don't", "stop"]);
+        assert_eq!(texts("3.14 is pi"), ["3.14", "is", "pi"]);
+    }
+
+    #[test]
+    fn keeps_korean_words_whole() {
+        let notes = "회의\t메모:\u{a0}다음 주\u{3000}월요일";
+
+        assert_eq!(texts(notes), ["회의", "메모", "다음", "주", "월요일"]);
+        assert_eq!(first_word(notes), Some("회의"));
+        assert_eq!(last_word(notes), Some("월요일"));
+        assert_eq!(nth_word(notes, 2), Some("다음"));
+    }
+
+    #[test]
+    fn finds_nothing_without_letters_or_digits() {
+        for s in ["", " ", "\t\n", "...", "!? -- ;", "\u{3000}"] {
+            assert_eq!(texts(s), Vec::<&str>::new(), "{s:?}");
+            assert_eq!(first_word(s), None);
+            assert_eq!(last_word(s), None);
+        }
+        assert_eq!(nth_word("one two", 2), None);
+    }
+
+    #[test]
+    fn walks_backwards_and_from_both_ends() {
+        let s = "  the quick, brown fox!  ";
+        let backwards: Vec<&str> = words(s).rev().map(|word| word.text).collect();
+        assert_eq!(backwards, ["fox", "brown", "quick", "the"]);
+
+        let mut both = words(s);
+        assert_eq!(both.next().map(|word| word.text), Some("the"));
+        assert_eq!(both.next_back().map(|word| word.text), Some("fox"));
+        assert_eq!(both.next_back().map(|word| word.text), Some("brown"));
+        assert_eq!(both.next().map(|word| word.text), Some("quick"));
+        assert_eq!(both.next(), None);
+        assert_eq!(both.next_back(), None);
+    }
+
+    #[test]
+    fn spans_are_byte_offsets_into_the_original() {
+        let s = "\u{a0}é 회의";
+        let spans: Vec<Range<usize>> = words(s).map(|word| word.span()).collect();
+
+        assert_eq!(spans, [2..4, 5..11]);
+        for word in words(s) {
+            assert_eq!(&s[word.span()], word.text);
+            assert_eq!(
+                word.start,
+                word.text.as_ptr() as usize - s.as_ptr() as usize
+            );
+        }
+    }
+}